@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+// Answer files live next to the inputs as `data/dayN_answers.txt`, one answer per line:
+//
+//     # part verdict answer
+//     1 accepted 865
+//     2 wrong 356439420655744
+//     2 accepted 352556672963116
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    Wrong,
+}
+
+impl Verdict {
+    fn parse(s: &str) -> Option<Verdict> {
+        match s {
+            "accepted" => Some(Verdict::Accepted),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Accepted => write!(f, "accepted"),
+            Verdict::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    KnownWrong,
+    Unverified,
+}
+
+impl Status {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Status::Pass | Status::Unverified)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::KnownWrong => "KNOWN-WRONG",
+            Status::Unverified => "UNVERIFIED",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartAnswers {
+    pub accepted: Option<String>,
+    pub wrong: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnswerStore {
+    parts: BTreeMap<u8, PartAnswers>,
+}
+
+impl AnswerStore {
+    // A missing file is an empty store, every answer is then unverified.
+    pub fn load(file_path: &Path) -> Result<AnswerStore, Box<dyn Error>> {
+        match fs::read_to_string(file_path) {
+            Ok(contents) => AnswerStore::parse(&contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(AnswerStore::default()),
+            Err(e) => Err(format!("{}: {}", file_path.display(), e).into()),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<AnswerStore, Box<dyn Error>> {
+        let mut store = AnswerStore::default();
+
+        for (line_num, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (part, verdict, answer) = match fields[..] {
+                [part, verdict, answer] => (part, verdict, answer),
+                _ => return Err(format!("Invalid answer line {}: {}", line_num + 1, line).into()),
+            };
+
+            let part = match part.parse::<u8>() {
+                Ok(part @ (1 | 2)) => part,
+                _ => return Err(format!("Invalid part on line {}: {}", line_num + 1, part).into()),
+            };
+            let verdict = Verdict::parse(verdict).ok_or(format!(
+                "Invalid verdict on line {}: {}",
+                line_num + 1,
+                verdict
+            ))?;
+
+            store.record(part, verdict, answer);
+        }
+
+        Ok(store)
+    }
+
+    pub fn record(&mut self, part: u8, verdict: Verdict, answer: &str) {
+        let entry = self.parts.entry(part).or_default();
+
+        match verdict {
+            Verdict::Accepted => entry.accepted = Some(answer.to_string()),
+            Verdict::Wrong => {
+                if !entry.wrong.iter().any(|w| w == answer) {
+                    entry.wrong.push(answer.to_string());
+                }
+            }
+        }
+    }
+
+    // Adds an answer to the file, keeping everything already in it.
+    pub fn append(
+        file_path: &Path,
+        part: u8,
+        verdict: Verdict,
+        answer: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let contents = match fs::read_to_string(file_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("{}: {}", file_path.display(), e).into()),
+        };
+
+        let separator = if contents.is_empty() || contents.ends_with('\n') {
+            ""
+        } else {
+            "\n"
+        };
+        let line = format!("{}{} {} {}\n", separator, part, verdict, answer);
+        fs::write(file_path, contents + &line)
+            .map_err(|e| format!("{}: {}", file_path.display(), e).into())
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartAnswers> {
+        self.parts.get(&part)
+    }
+
+    pub fn check(&self, part: u8, answer: &str) -> Status {
+        let Some(answers) = self.part(part) else {
+            return Status::Unverified;
+        };
+
+        match &answers.accepted {
+            Some(accepted) if accepted == answer => Status::Pass,
+            _ if answers.wrong.iter().any(|w| w == answer) => Status::KnownWrong,
+            Some(_) => Status::Fail,
+            None => Status::Unverified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STORE: &str = "\
+# part verdict answer
+1 accepted 865
+
+2 wrong 356439420655744
+2 accepted 352556672963116
+";
+
+    #[test]
+    fn check_answers() {
+        let store = AnswerStore::parse(STORE).unwrap();
+
+        assert_eq!(store.check(1, "865"), Status::Pass);
+        assert_eq!(store.check(1, "864"), Status::Fail);
+        assert_eq!(store.check(2, "356439420655744"), Status::KnownWrong);
+        assert_eq!(store.check(2, "352556672963116"), Status::Pass);
+    }
+
+    #[test]
+    fn unknown_part_is_unverified() {
+        let mut store = AnswerStore::default();
+        assert_eq!(store.check(1, "1"), Status::Unverified);
+
+        store.record(1, Verdict::Wrong, "2");
+        assert_eq!(store.check(1, "1"), Status::Unverified);
+        assert_eq!(store.check(1, "2"), Status::KnownWrong);
+    }
+
+    #[test]
+    fn reject_malformed_lines() {
+        assert!(AnswerStore::parse("1 accepted").is_err());
+        assert!(AnswerStore::parse("3 accepted 12").is_err());
+        assert!(AnswerStore::parse("1 maybe 12").is_err());
+    }
+
+    #[test]
+    fn load_names_the_file() {
+        let missing = Path::new("missing/day1_answers.txt");
+        assert_eq!(AnswerStore::load(missing).unwrap(), AnswerStore::default());
+
+        // A directory is there but cannot be read as a file.
+        let dir = std::env::temp_dir();
+        let e = AnswerStore::load(&dir).unwrap_err();
+        assert!(
+            e.to_string().starts_with(&format!("{}: ", dir.display())),
+            "{}",
+            e
+        );
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::bench::Stats;
+
+// Baselines are plain text, one benchmark per line with its durations in nanoseconds:
+//
+//     # day bench iterations min median mean p95 stddev
+//     1 parse 2511 106913 156699 152050 164866 19972
+//     1 part1 15243 18502 19225 19070 19275 6037
+
+// Welch's t above this is a real difference, about p < 0.001 for the sample sizes we take.
+const T_THRESHOLD: f64 = 3.29;
+// Changes of the median below this are not worth reporting, even if significant. Run to run
+// noise of the machine easily reaches a few percent.
+const MIN_CHANGE: f64 = 0.10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bench {
+    Parse,
+    Part(u8),
+}
+
+impl Bench {
+    fn parse(s: &str) -> Option<Bench> {
+        match s {
+            "parse" => Some(Bench::Parse),
+            "part1" => Some(Bench::Part(1)),
+            "part2" => Some(Bench::Part(2)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Bench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bench::Parse => write!(f, "parse"),
+            Bench::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Regression,
+    Improvement,
+    Unchanged,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Change::Regression => "regression",
+            Change::Improvement => "improvement",
+            Change::Unchanged => "unchanged",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Comparison {
+    pub baseline_median: Duration,
+    pub median: Duration,
+    pub change: Change,
+}
+
+impl Comparison {
+    // Relative change of the median, +0.1 is 10% slower than the baseline.
+    pub fn relative(&self) -> f64 {
+        let baseline = self.baseline_median.as_nanos() as f64;
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        (self.median.as_nanos() as f64 - baseline) / baseline
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "median {:?} -> {:?} ({:+.1}%, {})",
+            self.baseline_median,
+            self.median,
+            self.relative() * 100.0,
+            self.change
+        )
+    }
+}
+
+// Welch's t-test on the means decides whether there is a difference at all, the runs rarely have
+// the same number of iterations or variance. Its size is taken from the medians, which the odd
+// slow iteration doesn't move.
+pub fn compare(baseline: &Stats, current: &Stats) -> Comparison {
+    let mean_0 = baseline.mean.as_nanos() as f64;
+    let mean_1 = current.mean.as_nanos() as f64;
+    let var_0 = (baseline.stddev.as_nanos() as f64).powi(2) / baseline.iterations.max(1) as f64;
+    let var_1 = (current.stddev.as_nanos() as f64).powi(2) / current.iterations.max(1) as f64;
+
+    let error = (var_0 + var_1).sqrt();
+    let significant = if error == 0.0 {
+        mean_0 != mean_1
+    } else {
+        ((mean_1 - mean_0) / error).abs() > T_THRESHOLD
+    };
+
+    let mut comparison = Comparison {
+        baseline_median: baseline.median,
+        median: current.median,
+        change: Change::Unchanged,
+    };
+
+    let relative = comparison.relative();
+    if significant && relative >= MIN_CHANGE {
+        comparison.change = Change::Regression;
+    } else if significant && relative <= -MIN_CHANGE {
+        comparison.change = Change::Improvement;
+    }
+
+    comparison
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<(u8, Bench), Stats>,
+}
+
+impl Baseline {
+    // A missing file is an empty baseline, so the first save can go to a new file.
+    pub fn load(file_path: &Path) -> Result<Baseline, Box<dyn Error>> {
+        match fs::read_to_string(file_path) {
+            Ok(contents) => Baseline::parse(&contents),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(e) => Err(format!("{}: {}", file_path.display(), e).into()),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Baseline, Box<dyn Error>> {
+        let mut baseline = Baseline::default();
+
+        for (line_num, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let invalid = || format!("Invalid baseline line {}: {}", line_num + 1, line);
+            let [day, bench, numbers @ ..] = &fields[..] else {
+                return Err(invalid().into());
+            };
+
+            let day = day.parse::<u8>().map_err(|_| invalid())?;
+            let bench = Bench::parse(bench).ok_or_else(invalid)?;
+            let numbers = numbers
+                .iter()
+                .map(|n| n.parse::<u64>())
+                .collect::<Result<Vec<u64>, _>>()
+                .map_err(|_| invalid())?;
+            let [iterations, min, median, mean, p95, stddev] = numbers[..] else {
+                return Err(invalid().into());
+            };
+
+            let stats = Stats {
+                iterations: u32::try_from(iterations).map_err(|_| invalid())?,
+                min: Duration::from_nanos(min),
+                median: Duration::from_nanos(median),
+                mean: Duration::from_nanos(mean),
+                p95: Duration::from_nanos(p95),
+                stddev: Duration::from_nanos(stddev),
+            };
+            baseline.record(day, bench, stats);
+        }
+
+        Ok(baseline)
+    }
+
+    pub fn save(&self, file_path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(file_path, self.to_string())
+            .map_err(|e| format!("{}: {}", file_path.display(), e).into())
+    }
+
+    pub fn record(&mut self, day: u8, bench: Bench, stats: Stats) {
+        self.entries.insert((day, bench), stats);
+    }
+
+    pub fn get(&self, day: u8, bench: Bench) -> Option<&Stats> {
+        self.entries.get(&(day, bench))
+    }
+
+    pub fn compare(&self, day: u8, bench: Bench, current: &Stats) -> Option<Comparison> {
+        self.get(day, bench)
+            .map(|baseline| compare(baseline, current))
+    }
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# day bench iterations min median mean p95 stddev")?;
+        for ((day, bench), stats) in self.entries.iter() {
+            writeln!(
+                f,
+                "{} {} {} {} {} {} {} {}",
+                day,
+                bench,
+                stats.iterations,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.p95.as_nanos(),
+                stats.stddev.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(iterations: u32, mean: u64, stddev: u64) -> Stats {
+        Stats {
+            iterations,
+            min: Duration::from_nanos(mean - stddev),
+            median: Duration::from_nanos(mean),
+            mean: Duration::from_nanos(mean),
+            p95: Duration::from_nanos(mean + 2 * stddev),
+            stddev: Duration::from_nanos(stddev),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.record(8, Bench::Part(2), stats(100, 5000, 300));
+        baseline.record(1, Bench::Parse, stats(2511, 152050, 19972));
+
+        let parsed = Baseline::parse(&baseline.to_string()).unwrap();
+        assert_eq!(parsed, baseline);
+        assert!(parsed.get(1, Bench::Part(1)).is_none());
+    }
+
+    #[test]
+    fn reject_malformed_lines() {
+        assert!(Baseline::parse("1 parse 10 1 2 3 4").is_err());
+        assert!(Baseline::parse("1 part3 10 1 2 3 4 5").is_err());
+        assert!(Baseline::parse("1 parse 10 1 2 x 4 5").is_err());
+    }
+
+    #[test]
+    fn detect_changes() {
+        let base = stats(1000, 10_000, 500);
+
+        assert_eq!(
+            compare(&base, &stats(1000, 12_000, 500)).change,
+            Change::Regression
+        );
+        assert_eq!(
+            compare(&base, &stats(1000, 8_000, 500)).change,
+            Change::Improvement
+        );
+        // Significant, but too small to matter.
+        assert_eq!(
+            compare(&base, &stats(1000, 10_500, 500)).change,
+            Change::Unchanged
+        );
+        // Large, but lost in the noise of a handful of iterations.
+        assert_eq!(
+            compare(&base, &stats(10, 12_000, 8_000)).change,
+            Change::Unchanged
+        );
+    }
+}
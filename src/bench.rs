@@ -0,0 +1,262 @@
+use std::error::Error;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::trace;
+
+const WARMUP_TIME: Duration = Duration::from_millis(50);
+const MEASUREMENT_TIME: Duration = Duration::from_millis(300);
+const MIN_ITERATIONS: u32 = 10;
+const MAX_ITERATIONS: u32 = 100_000;
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    pub warmup_time: Duration,
+    pub measurement_time: Duration,
+    pub min_iterations: u32,
+    pub max_iterations: u32,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        BenchConfig {
+            warmup_time: WARMUP_TIME,
+            measurement_time: MEASUREMENT_TIME,
+            min_iterations: MIN_ITERATIONS,
+            max_iterations: MAX_ITERATIONS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub iterations: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+
+        let n = samples.len();
+        let total: Duration = samples.iter().sum();
+        let mean = total / n as u32;
+
+        let mean_nanos = mean.as_nanos() as f64;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_nanos() as f64 - mean_nanos).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let p95_index = ((n as f64 * 0.95).ceil() as usize).clamp(1, n) - 1;
+
+        Stats {
+            iterations: n as u32,
+            min: samples[0],
+            median,
+            mean,
+            p95: samples[p95_index],
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:?} | median {:?} | mean {:?} | p95 {:?} | stddev {:?} ({} iterations)",
+            self.min, self.median, self.mean, self.p95, self.stddev, self.iterations
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct BenchError {
+    pub iteration: u32,
+    pub source: Box<dyn Error>,
+}
+
+impl fmt::Display for BenchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Benchmark iteration {} failed: {}",
+            self.iteration, self.source
+        )
+    }
+}
+
+impl Error for BenchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+// How many iterations of `estimate` fill the measurement time, within the configured bounds.
+// There is always at least one sample to compute stats from, and a maximum below the minimum
+// counts as the minimum.
+fn iterations(config: &BenchConfig, estimate: Duration) -> u32 {
+    let min = config.min_iterations.max(1);
+    let max = config.max_iterations.max(min);
+
+    (config.measurement_time.as_nanos() / estimate.as_nanos().max(1))
+        .clamp(min as u128, max as u128) as u32
+}
+
+// Runs `f` until the warmup time is spent, then picks an iteration count that fills the
+// measurement time based on the warmup average. Any failing iteration aborts the benchmark.
+// Nothing is traced meanwhile.
+pub fn bench<T, F>(config: &BenchConfig, f: F) -> Result<Stats, BenchError>
+where
+    F: FnMut() -> Result<T, Box<dyn Error>>,
+{
+    trace::quiet(|| bench_quietly(config, f))
+}
+
+fn bench_quietly<T, F>(config: &BenchConfig, mut f: F) -> Result<Stats, BenchError>
+where
+    F: FnMut() -> Result<T, Box<dyn Error>>,
+{
+    let mut iteration = 0;
+    let mut run = |iteration: u32| -> Result<Duration, BenchError> {
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+
+        match result {
+            Ok(_) => Ok(duration),
+            Err(source) => Err(BenchError { iteration, source }),
+        }
+    };
+
+    let warmup_start = Instant::now();
+    let mut warmup_runs = 0;
+    while warmup_runs == 0 || warmup_start.elapsed() < config.warmup_time {
+        run(iteration)?;
+        iteration += 1;
+        warmup_runs += 1;
+    }
+    let iterations = iterations(config, warmup_start.elapsed() / warmup_runs);
+
+    let mut samples = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        samples.push(run(iteration)?);
+        iteration += 1;
+    }
+
+    Ok(Stats::from_samples(samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn micros(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|&s| Duration::from_micros(s)).collect()
+    }
+
+    #[test]
+    fn stats_of_samples() {
+        let stats = Stats::from_samples(micros(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.iterations, 5);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.mean, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        // The variance is 2 square microseconds.
+        assert_eq!(stats.stddev, Duration::from_nanos(1414));
+
+        let stats = Stats::from_samples(micros(&[8, 2, 4, 6]));
+        assert_eq!(stats.median, Duration::from_micros(5));
+        assert_eq!(stats.stddev, Duration::from_nanos(2236));
+
+        // The 95th percentile of 20 samples is the 19th, of 21 samples the 20th.
+        let samples: Vec<u64> = (1..=21).collect();
+        assert_eq!(
+            Stats::from_samples(micros(&samples[..20])).p95,
+            Duration::from_micros(19)
+        );
+        assert_eq!(
+            Stats::from_samples(micros(&samples)).p95,
+            Duration::from_micros(20)
+        );
+
+        let stats = Stats::from_samples(micros(&[7]));
+        assert_eq!((stats.median, stats.p95), (stats.min, stats.min));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn clamp_iterations() {
+        let config = BenchConfig::default();
+        assert_eq!(iterations(&config, Duration::from_millis(1)), 300);
+        assert_eq!(
+            iterations(&config, Duration::from_millis(100)),
+            MIN_ITERATIONS
+        );
+        assert_eq!(iterations(&config, Duration::from_nanos(1)), MAX_ITERATIONS);
+        assert_eq!(iterations(&config, Duration::ZERO), MAX_ITERATIONS);
+
+        let config = BenchConfig {
+            min_iterations: 0,
+            max_iterations: 0,
+            ..config
+        };
+        assert_eq!(iterations(&config, Duration::from_secs(1)), 1);
+        let config = BenchConfig {
+            min_iterations: 20,
+            max_iterations: 5,
+            ..config
+        };
+        assert_eq!(iterations(&config, Duration::from_nanos(1)), 20);
+    }
+
+    #[test]
+    fn bench_runs() {
+        let config = BenchConfig {
+            warmup_time: Duration::ZERO,
+            measurement_time: Duration::ZERO,
+            min_iterations: 3,
+            max_iterations: 5,
+        };
+
+        // One warmup run, then the minimum.
+        let mut runs = 0;
+        let stats = bench(&config, || {
+            runs += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!((stats.iterations, runs), (3, 4));
+
+        let mut runs = 0;
+        let e = bench(&config, || {
+            runs += 1;
+            if runs == 3 {
+                Err("broken".into())
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap_err();
+        assert_eq!(e.to_string(), "Benchmark iteration 2 failed: broken");
+
+        let config = BenchConfig {
+            min_iterations: 0,
+            ..config
+        };
+        let stats = bench(&config, || Ok(())).unwrap();
+        assert_eq!(stats.iterations, 1);
+    }
+}
@@ -1,109 +1,856 @@
 use std::error::Error;
-use std::fs;
-use std::time::Instant;
-
-fn read_input(file_path: &str) -> Result<Vec<i32>, Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
-
-    let rotations: Result<Vec<i32>, Box<dyn Error>> = contents
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|x| {
-            let mut chars = x.chars();
-            let sign = match chars.next() {
-                Some('R') => 1,
-                Some('L') => -1,
-                _ => return Err(format!("Invalid direction in line: {}", x).into()),
+use std::fmt::Display;
+use std::io::BufRead;
+
+use super::{InputKind, Solution};
+use crate::error::{ParseError, ParseErrorKind};
+use crate::parse;
+use crate::{debug, trace};
+
+// The first line of an input written in the extended syntax.
+pub const EXTENDED_HEADER: &str = "# syntax: extended";
+
+// Plain inputs are one `R48` or `L5` per line, blank lines aside. Extended ones, marked by
+// `EXTENDED_HEADER`, also take `+48` and `-5`, repeats like `3xR45` and `#` comments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    Plain,
+    Extended,
+}
+
+impl Syntax {
+    pub fn detect(contents: &str) -> Syntax {
+        match contents.lines().next() {
+            Some(line) if line.trim() == EXTENDED_HEADER => Syntax::Extended,
+            _ => Syntax::Plain,
+        }
+    }
+}
+
+// A rotation like `R48` or `L5`, right being positive, or in the extended syntax `+48` or `-5`.
+fn parse_rotation(contents: &str, x: &str, syntax: Syntax) -> Result<i64, ParseError> {
+    let mut chars = x.chars();
+    let sign = match (chars.next(), syntax) {
+        (Some('R'), _) | (Some('+'), Syntax::Extended) => 1,
+        (Some('L'), _) | (Some('-'), Syntax::Extended) => -1,
+        _ => {
+            return Err(ParseError::at(
+                contents,
+                x,
+                ParseErrorKind::InvalidDirection,
+            ));
+        }
+    };
+
+    // The direction is the only sign, `L-5` is no way of writing `R5`.
+    let magnitude = chars.as_str();
+    if magnitude.starts_with(['+', '-']) {
+        return Err(ParseError::at(contents, x, ParseErrorKind::InvalidNumber));
+    }
+    let value: u64 = parse::number(contents, magnitude)?;
+    let value = i64::try_from(value)
+        .map_err(|_| ParseError::at(contents, magnitude, ParseErrorKind::InvalidNumber))?;
+    Ok(sign * value)
+}
+
+// Most rotations the repeats of an input may expand to when it is read at once, which takes 8
+// bytes each. `rotations` streams any number of them.
+pub const MAX_ROTATIONS: usize = 10_000_000;
+
+// A rotation and how many times it is repeated, with the text of the count if it was written.
+struct Repeated<'a> {
+    count: u64,
+    count_text: Option<&'a str>,
+    rotation: i64,
+}
+
+// The rotation on a line, or `None` for a line without one.
+fn parse_line<'a>(
+    contents: &str,
+    line: &'a str,
+    syntax: Syntax,
+) -> Result<Option<Repeated<'a>>, ParseError> {
+    let line = match syntax {
+        Syntax::Plain => line,
+        Syntax::Extended => line.split('#').next().unwrap_or(""),
+    };
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let (count_text, rotation) = match line.split_once('x') {
+        Some((count, rotation)) if syntax == Syntax::Extended => {
+            (Some(count.trim_end()), rotation.trim_start())
+        }
+        _ => (None, line),
+    };
+    let count = match count_text {
+        Some(count) => parse::number(contents, count)?,
+        None => 1,
+    };
+
+    Ok(Some(Repeated {
+        count,
+        count_text,
+        rotation: parse_rotation(contents, rotation, syntax)?,
+    }))
+}
+
+pub fn read_rotations(contents: &str, syntax: Syntax) -> Result<Vec<i64>, ParseError> {
+    let mut rotations = vec![];
+    for line in contents.lines() {
+        let Some(repeated) = parse_line(contents, line, syntax)? else {
+            continue;
+        };
+        if let Some(count) = repeated.count_text
+            && repeated.count > (MAX_ROTATIONS.saturating_sub(rotations.len())) as u64
+        {
+            let kind = ParseErrorKind::TooMany {
+                limit: MAX_ROTATIONS,
             };
+            return Err(ParseError::at(contents, count, kind));
+        }
 
-            let value = chars.as_str().parse::<i32>()?;
-            Ok(sign * value)
-        })
-        .collect();
+        rotations.extend((0..repeated.count).map(|_| repeated.rotation));
+    }
 
-    rotations
+    Ok(rotations)
 }
 
-fn first_challenge(file_path: &str) -> Result<i32, Box<dyn Error>> {
-    let rotations = read_input(file_path)?;
+pub fn read_input(contents: &str) -> Result<Vec<i64>, ParseError> {
+    let syntax = Syntax::detect(contents);
+    let rotations = read_rotations(contents, syntax)?;
+    debug!("{:?} syntax, {} rotations", syntax, rotations.len());
 
-    let mut lock_state: i32 = 50;
+    Ok(rotations)
+}
 
-    let mut zero_count = 0;
+// The rotations of `reader`, read one line at a time into the same buffer, so even inputs too
+// big to load take constant memory. The syntax is detected from the first line, as for
+// `read_input`.
+pub struct Rotations<R> {
+    reader: R,
+    line: String,
+    line_num: usize,
+    syntax: Syntax,
+    // A repeated rotation and how many times it is still due.
+    pending: Option<(u64, i64)>,
+}
 
-    for rotation in rotations.iter() {
-        lock_state = (lock_state + rotation) % 100;
+pub fn rotations<R: BufRead>(reader: R) -> Rotations<R> {
+    Rotations {
+        reader,
+        line: String::new(),
+        line_num: 0,
+        syntax: Syntax::Plain,
+        pending: None,
+    }
+}
+
+impl<R: BufRead> Iterator for Rotations<R> {
+    type Item = Result<i64, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((repeat, rotation)) = self.pending.take()
+                && repeat > 0
+            {
+                self.pending = Some((repeat - 1, rotation));
+                return Some(Ok(rotation));
+            }
 
-        if lock_state == 0 {
-            zero_count += 1;
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => self.line_num += 1,
+                Err(e) => return Some(Err(e.into())),
+            }
+
+            let line = self.line.trim_end_matches(['\n', '\r']);
+            if self.line_num == 1 {
+                self.syntax = Syntax::detect(line);
+            }
+            // Located within the line, then moved to where the line is in the input.
+            match parse_line(line, line, self.syntax) {
+                Ok(repeated) => self.pending = repeated.map(|r| (r.count, r.rotation)),
+                Err(mut e) => {
+                    e.line = self.line_num;
+                    return Some(Err(e.into()));
+                }
+            }
         }
     }
+}
 
-    Ok(zero_count)
+// A dial numbered 0 to size - 1, turned right (positive) or left (negative) one click per step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Dial {
+    size: i64,
+    start: i64,
 }
 
-fn second_challenge(file_path: &str) -> Result<i32, Box<dyn Error>> {
-    let rotations = read_input(file_path)?;
+// One rotation of the dial, from one position to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub rotation: i64,
+    pub from: i64,
+    pub to: i64,
+}
+
+impl Dial {
+    // The dial of the safe in the puzzle.
+    pub const SAFE: Dial = Dial {
+        size: 100,
+        start: 50,
+    };
+
+    pub fn new(size: i64, start: i64) -> Result<Dial, Box<dyn Error>> {
+        if size <= 0 {
+            return Err(format!("Invalid dial size: {}", size).into());
+        }
+        if !(0..size).contains(&start) {
+            return Err(format!("Start {} is not on a dial of size {}", start, size).into());
+        }
+
+        Ok(Dial { size, start })
+    }
+
+    pub fn size(&self) -> i64 {
+        self.size
+    }
+
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    pub fn states<'a>(&self, rotations: &'a [i64]) -> impl Iterator<Item = Step> + 'a {
+        let size = self.size;
+
+        rotations
+            .iter()
+            .scan(self.start, move |position, &rotation| {
+                let from = *position;
+                *position = turn(from, rotation, size);
+                Some(Step {
+                    rotation,
+                    from,
+                    to: *position,
+                })
+            })
+    }
+
+    // How many clicks of `step` point the dial at `target`, the last one included.
+    pub fn clicks_onto(&self, step: &Step, target: i64) -> u64 {
+        // Clicks until the first time `target` comes up, going the way of the rotation.
+        let distance = match step.rotation >= 0 {
+            true => (target - step.from).rem_euclid(self.size),
+            false => (step.from - target).rem_euclid(self.size),
+        };
+        let first = if distance == 0 { self.size } else { distance };
+        let clicks = step.rotation.unsigned_abs();
+
+        match clicks >= first as u64 {
+            true => (clicks - first as u64) / self.size as u64 + 1,
+            false => 0,
+        }
+    }
+
+    // Rotations that leave the dial at `target`.
+    pub fn lands_on(&self, rotations: &[i64], target: i64) -> u64 {
+        self.states(rotations)
+            .filter(|step| step.to == target)
+            .count() as u64
+    }
+
+    // Clicks that point the dial at `target`, whether a rotation stops there or not. Summed in
+    // u128 like `DialCounter::passes`, as a few huge rotations overflow a u64.
+    pub fn passes_through(&self, rotations: &[i64], target: i64) -> u128 {
+        self.states(rotations)
+            .map(|step| self.clicks_onto(&step, target) as u128)
+            .sum()
+    }
+
+    // Turns the dial one click at a time, counting the clicks onto `target`. Slow but plainly
+    // right, which makes it the reference for `passes_through`.
+    pub fn simulate_clicks(&self, rotations: &[i64], target: i64) -> u128 {
+        let mut position = self.start;
+        let mut hits = 0;
+
+        for rotation in rotations.iter() {
+            for _ in 0..rotation.unsigned_abs() {
+                position = (position + rotation.signum()).rem_euclid(self.size);
+                if position == target {
+                    hits += 1;
+                }
+            }
+        }
 
-    let mut lock_state: i32 = 50;
+        hits
+    }
 
-    let mut zero_count = 0;
+    // What `lands_on` and `passes_through` count for every start of a dial this size, indexed by
+    // start. Rather than turning the dial once per start, every rotation adds a hit to all the
+    // starts it hits `target` from, so this takes one pass over the rotations.
+    pub fn every_start(&self, rotations: &[i64], target: i64) -> StartCounts {
+        let size = self.size;
+        let mut lands = vec![0; size as usize];
+        // Full laps hit the target from any start. The clicks left over do from a range of
+        // starts, added up over a difference array.
+        let mut laps: u128 = 0;
+        let mut partial = vec![0i64; size as usize + 1];
+        // How far the dial has turned from wherever it started.
+        let mut offset = 0;
 
-    for rotation in rotations.iter() {
-        let extra_zero_hits = (rotation / 100).abs();
-        let relative_movement = rotation % 100;
+        for &rotation in rotations.iter() {
+            let clicks = rotation.unsigned_abs();
+            laps += (clicks / size as u64) as u128;
+            let rest = (clicks % size as u64) as i64;
 
-        let relative_lock_state = lock_state + relative_movement;
+            // The leftover clicks hit the target when the dial was at most `rest` clicks before
+            // it, going the way of the rotation.
+            if rest > 0 {
+                let first = match rotation > 0 {
+                    true => target - rest - offset,
+                    false => target + 1 - offset,
+                };
+                let first = first.rem_euclid(size) as usize;
+                let last = first + rest as usize;
+                partial[first] += 1;
+                if last <= size as usize {
+                    partial[last] -= 1;
+                } else {
+                    partial[size as usize] -= 1;
+                    partial[0] += 1;
+                    partial[last - size as usize] -= 1;
+                }
+            }
 
-        let went_over_zero =
-            (lock_state != 0 && (relative_lock_state <= 0 || relative_lock_state >= 100)) as i32;
+            offset = turn(offset, rotation, size);
+            lands[(target - offset).rem_euclid(size) as usize] += 1;
+        }
 
-        lock_state = ((relative_lock_state % 100) + 100) % 100;
+        let passes = partial[..size as usize]
+            .iter()
+            .scan(0, |hits, change| {
+                *hits += change;
+                Some(laps + *hits as u128)
+            })
+            .collect();
 
-        zero_count += extra_zero_hits + went_over_zero;
+        StartCounts { lands, passes }
     }
 
-    Ok(zero_count)
+    // One line per rotation, with the positions it went between and how often it hit `target`.
+    pub fn trajectory(&self, rotations: &[i64], target: i64) -> String {
+        let width = (self.size - 1).to_string().len();
+
+        self.states(rotations)
+            .map(|step| {
+                let direction = if step.rotation < 0 { 'L' } else { 'R' };
+                format!(
+                    "{}{:<6} {:>w$} -> {:>w$}  {} x {}",
+                    direction,
+                    step.rotation.unsigned_abs(),
+                    step.from,
+                    step.to,
+                    self.clicks_onto(&step, target),
+                    target,
+                    w = width
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+// Where a dial of `size` at `from` ends up after `rotation`, which may be any i64.
+fn turn(from: i64, rotation: i64, size: i64) -> i64 {
+    (from + rotation % size).rem_euclid(size)
+}
+
+// Running counts of a dial turned one rotation at a time, for when the rotations are streamed
+// rather than collected. Passes are counted in u128, as on a small dial a few huge rotations
+// are enough to overflow a u64.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialCounter {
+    dial: Dial,
+    target: i64,
+    position: i64,
+    rotations: u64,
+    lands: u64,
+    passes: u128,
 }
 
-pub fn entry() {
-    println!("=== Day 1 ===");
+impl DialCounter {
+    pub fn new(dial: Dial, target: i64) -> DialCounter {
+        DialCounter {
+            dial,
+            target,
+            position: dial.start(),
+            rotations: 0,
+            lands: 0,
+            passes: 0,
+        }
+    }
+
+    pub fn turn(&mut self, rotation: i64) -> Step {
+        let step = Step {
+            rotation,
+            from: self.position,
+            to: turn(self.position, rotation, self.dial.size()),
+        };
 
-    let file_path_test = "data/day1_test.txt";
-    let file_path = "data/day1.txt";
-    let iterations = 100;
+        self.position = step.to;
+        self.rotations += 1;
+        if step.to == self.target {
+            self.lands += 1;
+        }
+        self.passes += self.dial.clicks_onto(&step, self.target) as u128;
 
-    println!("=== Challenge 1 ===");
-    match first_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+        step
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = first_challenge(file_path);
+    pub fn position(&self) -> i64 {
+        self.position
     }
-    let duration = start.elapsed();
-    match first_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    pub fn rotations(&self) -> u64 {
+        self.rotations
     }
-    println!("Average time on {:?} iterations: {:?}", iterations, duration / iterations);
 
-    println!("=== Challenge 2 ===");
-    match second_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    // The part 1 count so far, rotations that stopped on the target.
+    pub fn lands(&self) -> u64 {
+        self.lands
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = second_challenge(file_path);
+    // The part 2 count so far, clicks onto the target.
+    pub fn passes(&self) -> u128 {
+        self.passes
     }
-    let duration = start.elapsed();
-    match second_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+}
+
+// Hits of the target by start, see `Dial::every_start`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartCounts {
+    pub lands: Vec<u64>,
+    pub passes: Vec<u128>,
+}
+
+// The starts with the most and with the fewest hits in `counts`, the lowest start on ties.
+pub fn extremes<T: Copy + Ord>(counts: &[T]) -> Option<((i64, T), (i64, T))> {
+    let starts = counts
+        .iter()
+        .enumerate()
+        .map(|(start, hits)| (start as i64, *hits));
+
+    let best = starts.clone().rev().max_by_key(|(_, hits)| *hits)?;
+    let worst = starts.min_by_key(|(_, hits)| *hits)?;
+    Some((best, worst))
+}
+
+fn start_line<T: Copy + Ord + Display>(part: u8, counts: &[T], start: i64) -> Option<String> {
+    let ((best, most), (worst, fewest)) = extremes(counts)?;
+    Some(format!(
+        "Part {}: best start {} ({}), worst start {} ({}), start {} ({})",
+        part, best, most, worst, fewest, start, counts[start as usize]
+    ))
+}
+
+// How the counts of both parts would change with the start of the safe's dial.
+pub fn start_report(rotations: &[i64]) -> String {
+    let dial = Dial::SAFE;
+    let counts = dial.every_start(rotations, 0);
+
+    let mut lines = vec![format!(
+        "Zeros for every start of a dial of {}, after {} rotations:",
+        dial.size(),
+        rotations.len()
+    )];
+    lines.extend(start_line(1, &counts.lands, dial.start()));
+    lines.extend(start_line(2, &counts.passes, dial.start()));
+
+    lines.join("\n")
+}
+
+pub fn first_challenge(rotations: &[i64]) -> Result<u128, Box<dyn Error>> {
+    Ok(Dial::SAFE.lands_on(rotations, 0).into())
+}
+
+pub fn second_challenge(rotations: &[i64]) -> Result<u128, Box<dyn Error>> {
+    trace!("Trajectory:\n{}", Dial::SAFE.trajectory(rotations, 0));
+
+    Ok(Dial::SAFE.passes_through(rotations, 0))
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+    type Output = u128;
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn name(&self) -> &'static str {
+        "Secret Entrance"
+    }
+
+    fn parse(&self, contents: &str, _kind: InputKind) -> Result<Self::Input, ParseError> {
+        read_input(contents)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        first_challenge(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        second_challenge(input)
+    }
+
+    fn analyse(&self, input: &Self::Input) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(Some(start_report(input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../data/day1_test.txt");
+
+    #[test]
+    fn first_challenge_example() {
+        let input = Day1.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day1.part1(&input).unwrap(), 3);
+    }
+
+    #[test]
+    fn second_challenge_example() {
+        let input = Day1.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day1.part2(&input).unwrap(), 6);
+    }
+
+    #[test]
+    fn count_other_targets() {
+        let input = Day1.parse(EXAMPLE, InputKind::Example).unwrap();
+        let dial = Dial::new(100, 50).unwrap();
+        assert_eq!(dial, Dial::SAFE);
+
+        // The example stops on 82, 52, 0, 95, 55, 0, 99, 0, 14 and 32.
+        assert_eq!(dial.lands_on(&input, 32), 1);
+        assert_eq!(dial.lands_on(&input, 50), 0);
+        assert_eq!(dial.passes_through(&input, 32), 5);
+        assert_eq!(dial.passes_through(&input, 50), 4);
+        assert_eq!(dial.passes_through(&input, 99), 6);
+    }
+
+    #[test]
+    fn small_dial() {
+        let dial = Dial::new(5, 2).unwrap();
+        let rotations = [3, -12, 5];
+
+        let states: Vec<i64> = dial.states(&rotations).map(|step| step.to).collect();
+        assert_eq!(states, [0, 3, 3]);
+        assert_eq!(dial.lands_on(&rotations, 3), 2);
+        // 3 4 0 | 2 1 0 4 3 2 1 0 4 3 | 4 0 1 2 3
+        assert_eq!(dial.passes_through(&rotations, 0), 4);
+        assert_eq!(dial.passes_through(&rotations, 3), 5);
+        assert_eq!(
+            dial.trajectory(&rotations, 0),
+            "R3      2 -> 0  1 x 0\nL12     0 -> 3  2 x 0\nR5      3 -> 3  1 x 0"
+        );
+
+        assert!(Dial::new(0, 0).is_err());
+        assert!(Dial::new(5, 5).is_err());
+    }
+
+    // SplitMix64, so the randomized tests need no dependencies and replay the same cases.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        }
+
+        fn below(&mut self, n: u64) -> u64 {
+            self.next() % n
+        }
+    }
+
+    // Rotations mixing small and large turns, exact multiples of the dial and turns that stop
+    // on 0 or `target`, so the next one starts there.
+    fn random_rotations(rng: &mut Rng, dial: &Dial, target: i64) -> Vec<i64> {
+        let size = dial.size() as u64;
+        let mut position = dial.start();
+        let mut rotations = vec![];
+
+        for _ in 0..rng.below(30) {
+            let sign = if rng.below(2) == 0 { 1 } else { -1 };
+            let rotation = match rng.below(5) {
+                0 => sign * rng.below(size) as i64,
+                1 => sign * rng.below(20 * size) as i64,
+                2 => sign * (rng.below(10) * size) as i64,
+                // Onto 0 or the target, with a few extra laps.
+                _ => {
+                    let to = if rng.below(2) == 0 { 0 } else { target };
+                    let laps = (rng.below(3) * size) as i64;
+                    match sign {
+                        1 => (to - position).rem_euclid(size as i64) + laps,
+                        _ => -((position - to).rem_euclid(size as i64) + laps),
+                    }
+                }
+            };
+
+            position = (position + rotation).rem_euclid(size as i64);
+            rotations.push(rotation);
+        }
+
+        rotations
+    }
+
+    #[test]
+    fn formula_matches_simulation() {
+        let mut rng = Rng(2025);
+
+        for case in 0..500 {
+            let dial = match case % 3 {
+                0 => Dial::SAFE,
+                _ => {
+                    let size = rng.below(120) as i64 + 1;
+                    Dial::new(size, rng.below(size as u64) as i64).unwrap()
+                }
+            };
+            let target = match case % 2 {
+                0 => 0,
+                _ => rng.below(dial.size() as u64) as i64,
+            };
+            let rotations = random_rotations(&mut rng, &dial, target);
+
+            assert_eq!(
+                dial.passes_through(&rotations, target),
+                dial.simulate_clicks(&rotations, target),
+                "case {}: {:?}, target {}, rotations {:?}",
+                case,
+                dial,
+                target,
+                rotations
+            );
+            if dial == Dial::SAFE && target == 0 {
+                assert_eq!(
+                    second_challenge(&rotations).unwrap(),
+                    dial.simulate_clicks(&rotations, 0),
+                    "case {}: rotations {:?}",
+                    case,
+                    rotations
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_start_at_once() {
+        let mut rng = Rng(25);
+
+        for case in 0..200 {
+            let size = rng.below(40) as i64 + 1;
+            let dial = Dial::new(size, 0).unwrap();
+            let target = rng.below(size as u64) as i64;
+            let rotations = random_rotations(&mut rng, &dial, target);
+
+            let counts = dial.every_start(&rotations, target);
+            for start in 0..size {
+                let dial = Dial::new(size, start).unwrap();
+                let expected = (
+                    dial.lands_on(&rotations, target),
+                    dial.passes_through(&rotations, target),
+                );
+                let found = (counts.lands[start as usize], counts.passes[start as usize]);
+                assert_eq!(
+                    found, expected,
+                    "case {}, start {}: {:?}",
+                    case, start, rotations
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn best_and_worst_start() {
+        assert_eq!(extremes(&[2, 5, 1, 5, 1]), Some(((1, 5), (2, 1))));
+        assert_eq!(extremes::<u64>(&[]), None);
+
+        let input = Day1.parse(EXAMPLE, InputKind::Example).unwrap();
+        let report = start_report(&input);
+        assert!(report.contains("start 50 (3)"), "{}", report);
+        assert!(report.contains("start 50 (6)"), "{}", report);
+    }
+
+    #[test]
+    fn stream_rotations() {
+        let reader = std::io::Cursor::new(EXAMPLE);
+        let mut counter = DialCounter::new(Dial::SAFE, 0);
+        let mut counts = vec![];
+        for rotation in rotations(reader) {
+            counter.turn(rotation.unwrap());
+            counts.push((counter.lands(), counter.passes()));
+        }
+
+        assert_eq!(counter.rotations(), 10);
+        assert_eq!(counts[2], (1, 2));
+        assert_eq!(counts[9], (3, 6));
+        assert_eq!(counter.position(), 32);
+
+        let reader = std::io::Cursor::new("R1\n\nL2\nX3\n");
+        let e = rotations(reader).nth(2).unwrap().unwrap_err();
+        assert_eq!(e.to_string(), "4:1: Invalid direction: X3");
+
+        // A sign after the direction is an error, not a turn the other way.
+        let reader = std::io::Cursor::new("R1\nL2\nL-5\nR4\n");
+        let streamed: Vec<String> = rotations(reader)
+            .map(|r| r.map_or_else(|e| e.to_string(), |r| r.to_string()))
+            .collect();
+        assert_eq!(streamed, ["1", "-2", "3:1: Invalid number: L-5", "4"]);
+    }
+
+    const EXTENDED: &str = "\
+# syntax: extended
+-68 # the example, rewritten
+L30
++48
+
+# blank lines and comments are skipped
+L5
+R60
+L55 L1
+";
+
+    #[test]
+    fn extended_syntax() {
+        assert_eq!(Syntax::detect(EXAMPLE), Syntax::Plain);
+        assert_eq!(Syntax::detect(EXTENDED), Syntax::Extended);
+
+        let input = EXTENDED.replace("L55 L1", "L55\n2 x -1\n# L99\n\n3xR2");
+        let rotations = read_rotations(&input, Syntax::Extended).unwrap();
+        assert_eq!(rotations, [-68, -30, 48, -5, 60, -55, -1, -1, 2, 2, 2]);
+        assert_eq!(read_input(&input).unwrap(), rotations);
+
+        // The original format stays as it was.
+        assert_eq!(read_rotations(EXAMPLE, Syntax::Plain).unwrap().len(), 10);
+        let e = read_input("R1\n+5\n").unwrap_err();
+        assert_eq!(e.to_string(), "2:1: Invalid direction: +5");
+        let e = read_input("R1\n3xR5\n").unwrap_err();
+        assert_eq!(e.to_string(), "2:1: Invalid direction: 3xR5");
+    }
+
+    #[test]
+    fn signed_magnitudes() {
+        let error = |input: &str| read_input(input).unwrap_err().to_string();
+
+        for rotation in ["L-5", "R+5"] {
+            let input = format!("R1\n{}\n", rotation);
+            assert_eq!(error(&input), format!("2:1: Invalid number: {}", rotation));
+            let input = format!("{}\n{}\n", EXTENDED_HEADER, rotation);
+            assert_eq!(error(&input), format!("2:1: Invalid number: {}", rotation));
+        }
+        for rotation in ["--5", "+-5"] {
+            let input = format!("R1\n{}\n", rotation);
+            assert_eq!(
+                error(&input),
+                format!("2:1: Invalid direction: {}", rotation)
+            );
+            let input = format!("{}\n{}\n", EXTENDED_HEADER, rotation);
+            assert_eq!(error(&input), format!("2:1: Invalid number: {}", rotation));
+        }
+        let input = format!("{}\n3x-+5\n", EXTENDED_HEADER);
+        assert_eq!(error(&input), "2:3: Invalid number: -+5");
+
+        assert_eq!(read_input("R9223372036854775807").unwrap(), [i64::MAX]);
+        assert_eq!(
+            error("L9223372036854775808"),
+            "1:2: Invalid number: 9223372036854775808"
+        );
+    }
+
+    #[test]
+    fn extended_errors() {
+        let error = |line: &str| {
+            let input = EXTENDED.replace("L55 L1", line);
+            read_input(&input).unwrap_err().to_string()
+        };
+
+        assert_eq!(error("L55 L1"), "9:2: Invalid number: 55 L1");
+        assert_eq!(error("  *4 # not a rotation"), "9:3: Invalid direction: *4");
+        assert_eq!(error("yxR4"), "9:1: Invalid number: y");
+        assert_eq!(error("3x"), "9:3: Invalid direction");
+        assert_eq!(error("+"), "9:2: Invalid number");
+
+        // Huge repeats are refused rather than expanded, those beyond a u64 are no number.
+        assert_eq!(
+            error("18446744073709551615xR1"),
+            "9:1: More than 10000000 entries: 18446744073709551615"
+        );
+        assert_eq!(
+            error("18446744073709551616xR1"),
+            "9:1: Invalid number: 18446744073709551616"
+        );
+        // The limit is on the whole input, the 6 rotations before count too.
+        assert_eq!(
+            error("L1 # one more\n 9999995 x R1"),
+            "10:2: More than 10000000 entries: 9999995"
+        );
+    }
+
+    #[test]
+    fn stream_extended() {
+        let input = EXTENDED.replace("L55 L1", "L55\nL1\n3xR100");
+        let streamed: Vec<i64> = rotations(std::io::Cursor::new(input.clone()))
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(streamed, read_input(&input).unwrap());
+
+        // A repeat is handed out one rotation at a time, never expanded.
+        let input = format!("{}\n{}xR1\n", EXTENDED_HEADER, u64::MAX);
+        let mut streamed = rotations(std::io::Cursor::new(input));
+        assert_eq!(streamed.nth(1000).map(|r| r.unwrap()), Some(1));
+
+        let input = EXTENDED.replace("L55 L1", "L55\nL1 R2");
+        let e = rotations(std::io::Cursor::new(input))
+            .nth(6)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(e.to_string(), "10:2: Invalid number: 1 R2");
+    }
+
+    #[test]
+    fn huge_rotations() {
+        let input = format!("R{}\nL{}\nR{}\n", i64::MAX, i64::MAX, i64::MAX);
+        let rotations: Vec<i64> = read_input(&input).unwrap();
+        let mut counter = DialCounter::new(Dial::SAFE, 0);
+        for rotation in rotations.iter() {
+            counter.turn(*rotation);
+        }
+
+        // 9223372036854775807 clicks from 50 end on 57, with a zero every 100 clicks from the 50th.
+        let from_50 = (i64::MAX as u64 - 50) / 100 + 1;
+        let from_57 = (i64::MAX as u64 - 57) / 100 + 1;
+        assert_eq!(Dial::SAFE.states(&rotations).next().unwrap().to, 57);
+        assert_eq!(counter.position(), 57);
+        assert_eq!(counter.passes(), (from_50 + 2 * from_57) as u128);
+
+        // On a dial with a single position every click counts.
+        let mut counter = DialCounter::new(Dial::new(1, 0).unwrap(), 0);
+        for rotation in rotations.iter() {
+            counter.turn(*rotation);
+        }
+        assert_eq!(counter.passes(), 3 * i64::MAX as u128);
+        let dial = Dial::new(1, 0).unwrap();
+        assert_eq!(dial.passes_through(&rotations, 0), counter.passes());
+        assert_eq!(dial.every_start(&rotations, 0).passes, [counter.passes()]);
     }
-    println!("Average time on {:?} iterations: {:?}", iterations, duration / iterations);
 }
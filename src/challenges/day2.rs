@@ -1,43 +1,18 @@
 use std::collections::HashSet;
 use std::error::Error;
-use std::fs;
 use std::num::ParseIntError;
-use std::time::Instant;
 
-fn read_input(file_path: &str) -> Result<Vec<(u64, u64)>, Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
+use super::{InputKind, Solution};
+use crate::error::{ParseError, ParseErrorKind};
+use crate::parse;
+use crate::{debug, trace};
 
-    let ranges: Result<Vec<(u64, u64)>, Box<dyn Error>> = contents
-        .lines()
-        .filter(|line| !line.is_empty())
+pub fn read_input(contents: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let line = parse::lines(contents)
         .next()
-        .ok_or("No valid lines found")?
-        .split(',')
-        .filter(|line| !line.is_empty())
-        .map(|pair| {
-            let parts: Vec<&str> = pair.split("-").collect();
-
-            if parts.len() != 2 {
-                return Err(format!("Invalid range format: {}", pair).into());
-            }
-
-            let start = parts[0].parse::<u64>();
-
-            match &start {
-                Ok(_) => (),
-                Err(e) => eprintln!("Start is not good: {:?}, {:?}", parts[0], e),
-            };
-            let end = parts[1].parse::<u64>();
-            match &end {
-                Ok(_) => (),
-                Err(e) => eprintln!("End is not good: {:?}, {:?}", parts[1], e),
-            };
+        .ok_or(ParseError::eof(contents, ParseErrorKind::Empty))?;
 
-            Ok((start?, end?))
-        })
-        .collect();
-
-    ranges
+    parse::ranges(contents, line, ',')
 }
 
 fn get_range_bounds_by_repetition(
@@ -51,8 +26,7 @@ fn get_range_bounds_by_repetition(
     let start_len = range_start_str.len();
     let end_len = range_end_str.len();
 
-    let lower_bound: u64;
-    if start_len % repeat == 0 {
+    let lower_bound = if start_len.is_multiple_of(repeat) {
         let part_size = start_len / repeat;
 
         let first = &range_start_str[..part_size].parse::<u64>()?;
@@ -69,18 +43,16 @@ fn get_range_bounds_by_repetition(
 
         let vec = rest?;
 
-        lower_bound = vec
-            .iter()
+        vec.iter()
             .find(|&&n| first != &n)
             .map(|n| if first > n { *first } else { *first + 1 })
-            .unwrap_or(*first);
+            .unwrap_or(*first)
     } else {
         let base: u64 = 10;
-        lower_bound = base.pow((start_len / repeat) as u32);
-    }
+        base.pow((start_len / repeat) as u32)
+    };
 
-    let upper_bound: u64;
-    if end_len % repeat == 0 {
+    let upper_bound = if end_len.is_multiple_of(repeat) {
         let part_size = end_len / repeat;
 
         let first = &range_end_str[..part_size].parse::<u64>()?;
@@ -95,24 +67,22 @@ fn get_range_bounds_by_repetition(
             .collect();
 
         let vec = rest?;
-        
-        upper_bound = vec
-            .iter()
+
+        vec.iter()
             .find(|&&n| first != &n)
             .map(|n| if first < n { *first } else { *first - 1 })
-            .unwrap_or(*first);
+            .unwrap_or(*first)
     } else {
         let base: u64 = 10;
-        upper_bound = base.pow((end_len / repeat) as u32) - 1;
-    }
-    // println!("Bounds: {:?}-{:?} r{:?}", lower_bound, upper_bound, repeat);
+        base.pow((end_len / repeat) as u32) - 1
+    };
+    trace!("Bounds: {}-{} r{}", lower_bound, upper_bound, repeat);
     Ok((lower_bound, upper_bound))
 }
 
-fn get_range_bounds(
-    range_start: u64,
-    range_end: u64,
-) -> Result<Vec<(u64, u64, usize)>, Box<dyn Error>> {
+type RepeatBounds = (u64, u64, usize);
+
+fn get_range_bounds(range_start: u64, range_end: u64) -> Result<Vec<RepeatBounds>, Box<dyn Error>> {
     let range_end_str = range_end.to_string();
 
     let end_len = range_end_str.len();
@@ -128,9 +98,7 @@ fn get_range_bounds(
         .collect()
 }
 
-fn first_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let ranges = read_input(file_path)?;
-
+pub fn first_challenge(ranges: &[(u64, u64)]) -> Result<u64, Box<dyn Error>> {
     let mut acc: u64 = 0;
     for range in ranges.iter() {
         let (lower_bound, upper_bound) = get_range_bounds_by_repetition(range.0, range.1, 2)?;
@@ -144,27 +112,24 @@ fn first_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
     Ok(acc)
 }
 
-fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let ranges = read_input(file_path)?;
-
+pub fn second_challenge(ranges: &[(u64, u64)]) -> Result<u64, Box<dyn Error>> {
     let mut acc: u64 = 0;
 
     let mut seen_numbers: HashSet<u64> = HashSet::new();
     for range in ranges.iter() {
-        // println!("Range: {:?} {:?}", range.0, range.1);
+        debug!("Range: {}-{}", range.0, range.1);
         let range_bounds = get_range_bounds(range.0, range.1)?;
 
         for (range_start, range_end, repeat) in range_bounds.iter() {
-            // println!("\tBounds: {:?}-{:?} r{:?}", range_start, range_end, repeat);
+            debug!("Repeating {} times: {}-{}", repeat, range_start, range_end);
             for n in *range_start..=*range_end {
-                let r = n.to_string().repeat(*repeat as usize).parse::<u64>()?;
+                let r = n.to_string().repeat(*repeat).parse::<u64>()?;
 
-                if !seen_numbers.contains(&r) {
-                    seen_numbers.insert(r);
+                if seen_numbers.insert(r) {
                     acc += r;
-                    // println!("\t: {:?}", r);
+                    trace!("Invalid ID: {}", r);
                 } else {
-                    // println!("\talready found: {:?}", r);
+                    trace!("Already found: {}", r);
                 }
             }
         }
@@ -173,52 +138,48 @@ fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
     Ok(acc)
 }
 
-pub fn entry() {
-    println!("=== Day 2 ===");
+pub struct Day2;
 
-    let file_path_test = "data/day2_test.txt";
-    let file_path = "data/day2.txt";
-    let iterations = 100;
+impl Solution for Day2 {
+    type Input = Vec<(u64, u64)>;
+    type Output = u64;
 
-    println!("=== Challenge 1 ===");
-    match first_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    fn day(&self) -> u8 {
+        2
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = first_challenge(file_path);
+    fn name(&self) -> &'static str {
+        "Gift Shop"
     }
-    let duration = start.elapsed();
-    match first_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    fn parse(&self, contents: &str, _kind: InputKind) -> Result<Self::Input, ParseError> {
+        read_input(contents)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        first_challenge(input)
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
-
-    println!("=== Challenge 2 ===");
-    match second_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        second_challenge(input)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = second_challenge(file_path);
+    const EXAMPLE: &str = include_str!("../../data/day2_test.txt");
+
+    #[test]
+    fn first_challenge_example() {
+        let input = Day2.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day2.part1(&input).unwrap(), 1227775554);
     }
-    let duration = start.elapsed();
-    match second_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    #[test]
+    fn second_challenge_example() {
+        let input = Day2.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day2.part2(&input).unwrap(), 4174379265);
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
 }
@@ -1,53 +1,35 @@
 use std::error::Error;
-use std::fs;
-use std::time::Instant;
-
-fn read_input(file_path: &str) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
-
-    let banks: Result<Vec<Vec<u8>>, Box<dyn Error>> = contents
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let bank: Result<Vec<u8>, _> = line
-                .split("")
-                .filter(|s| !s.is_empty())
-                .map(|s| s.parse::<u8>())
-                .collect();
-            
-            bank.map_err(|e| e.into())
-        })
-        .collect();
-
-    banks
-}
 
-fn first_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let banks = read_input(file_path)?;
+use super::{InputKind, Solution};
+use crate::error::ParseError;
+use crate::parse;
+use crate::{debug, trace};
+
+pub fn read_input(contents: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    parse::digit_rows(contents, contents)
+}
 
+pub fn first_challenge(banks: &[Vec<u8>]) -> Result<u64, Box<dyn Error>> {
     let mut accumulator: u64 = 0;
     for bank in banks.iter() {
         let l = bank.len();
 
-        let j = bank
-            .iter()
-            .enumerate()
-            .fold((0 as u8, 0 as u8), |acc, (i, x)| {
-                if *x > acc.0 && i != l - 1 {
-                    (*x, 0)
-                } else {
-                    if *x > acc.1 { (acc.0, *x) } else { acc }
-                }
-            });
+        let j = bank.iter().enumerate().fold((0_u8, 0_u8), |acc, (i, x)| {
+            if *x > acc.0 && i != l - 1 {
+                (*x, 0)
+            } else {
+                if *x > acc.1 { (acc.0, *x) } else { acc }
+            }
+        });
         accumulator += j.0 as u64 * 10 + j.1 as u64;
     }
 
     Ok(accumulator)
 }
 
-fn find_optimal_joltage(bank: &Vec<u8>, num: usize) -> u64 {
+fn find_optimal_joltage(bank: &[u8], num: usize) -> u64 {
     let l = bank.len();
-    // println!("bank_l, num: {:?}, {:?}", l, num);
+    trace!("Picking {} of {} batteries", num, l);
 
     let optimal_batteries: Vec<u8> = vec![0; num];
 
@@ -56,98 +38,86 @@ fn find_optimal_joltage(bank: &Vec<u8>, num: usize) -> u64 {
         .enumerate()
         .fold(optimal_batteries, |acc, (i, x)| {
             let mut new_acc = acc.clone();
-            // println!("i, x: {:?}, {:?}", i, x);
-            // println!("\tacc: {:?}", acc);
             for (j, battery) in acc.iter().enumerate() {
-                // println!("\tj, battery: {:?}, {:?}", j, battery);
+                // Leave enough batteries after this one to fill the remaining slots.
                 let max_i = l - num + j + 1;
-                // println!("\tmax_i: {:?}", max_i);
                 if *x > *battery && i < max_i {
-                    // println!("\t*x > *battery true!");
-                    
+                    trace!("Battery {} ({}) replaces slot {} ({})", i, x, j, battery);
+
                     new_acc[j] = *x;
-                    for k in (j + 1)..num {
-                        new_acc[k] = 0;
+                    for battery in new_acc.iter_mut().skip(j + 1) {
+                        *battery = 0;
                     }
                     break;
                 }
             }
-            // println!("\tnew_acc: {:?}", new_acc);
+            trace!("Picked after battery {}: {:?}", i, new_acc);
             new_acc
         });
 
-    // println!("j: {:?}", j);
+    debug!("Picked {:?}", j);
 
-    let a = j.iter().enumerate().fold(0, |acc, (i , x)| {
+    j.iter().enumerate().fold(0, |acc, (i, x)| {
         let base: u64 = 10;
-        let m = base.pow((num-1-i) as u32);
+        let m = base.pow((num - 1 - i) as u32);
 
         acc + m * *x as u64
-
-    });
-
-    a
+    })
 }
 
-fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let banks = read_input(file_path)?;
-
+pub fn second_challenge(banks: &[Vec<u8>]) -> Result<u64, Box<dyn Error>> {
     let mut accumulator: u64 = 0;
     for bank in banks.iter() {
         let optimal_joltage = find_optimal_joltage(bank, 12);
         accumulator += optimal_joltage;
-        // println!("Optimal joltage: {:?}", optimal_joltage)
+        debug!("Optimal joltage: {}", optimal_joltage);
     }
 
     Ok(accumulator)
 }
 
-pub fn entry() {
-    println!("=== Day 3 ===");
+pub struct Day3;
 
-    let file_path_test = "data/day3_test.txt";
-    let file_path = "data/day3.txt";
-    let iterations = 100;
+impl Solution for Day3 {
+    type Input = Vec<Vec<u8>>;
+    type Output = u64;
 
-    println!("=== Challenge 1 ===");
-    match first_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    fn day(&self) -> u8 {
+        3
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = first_challenge(file_path);
+    fn name(&self) -> &'static str {
+        "Lobby"
     }
-    let duration = start.elapsed();
-    match first_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    fn parse(&self, contents: &str, _kind: InputKind) -> Result<Self::Input, ParseError> {
+        read_input(contents)
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
-
-    println!("=== Challenge 2 ===");
-    match second_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        first_challenge(input)
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = second_challenge(file_path);
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        second_challenge(input)
     }
-    let duration = start.elapsed();
-    match second_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../data/day3_test.txt");
+
+    #[test]
+    fn first_challenge_example() {
+        let input = Day3.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day3.part1(&input).unwrap(), 357);
+    }
+
+    #[test]
+    fn second_challenge_example() {
+        let input = Day3.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day3.part2(&input).unwrap(), 3121910778619);
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
 }
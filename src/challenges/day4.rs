@@ -1,201 +1,109 @@
 use std::error::Error;
-use std::fs;
-use std::time::Instant;
-
-fn read_input(file_path: &str) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
-
-    let roll_layout: Result<Vec<Vec<u8>>, Box<dyn Error>> = contents
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            line.chars()
-                .map(|c| match c {
-                    '.' => Ok(0),
-                    '@' => Ok(1),
-                    _ => Err(format!("Invalid char: {}", c).into()),
-                })
-                .collect()
-        })
-        .collect();
-
-    roll_layout
-}
-
-fn zero_padding(matrix: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
-    let w = matrix[0].len();
-
-    let mut padded_matrix: Vec<Vec<u8>> = matrix
-        .iter()
-        .map(|row| {
-            let mut new_row = row.clone();
-            new_row.insert(0, 0);
-            new_row.push(0);
 
-            new_row
-        })
-        .collect();
+use super::{InputKind, Solution};
+use crate::debug;
+use crate::error::ParseError;
+use crate::grid::{ALL_AROUND, Border, Grid, Pos};
+use crate::parse;
 
-    padded_matrix.insert(0, vec![0; w + 2]);
-    padded_matrix.push(vec![0; w + 2]);
-    // println!("padded_matrix: {:?}", padded_matrix);
-
-    return padded_matrix;
-}
+pub fn read_input(contents: &str) -> Result<Grid<u8>, ParseError> {
+    let rows = parse::char_grid(contents, contents, |c| match c {
+        '.' => Some(0),
+        '@' => Some(1),
+        _ => None,
+    })?;
 
-fn count_adjacent(matrix: Vec<Vec<u8>>) -> u64 {
-    matrix.iter().enumerate().fold(0, |acc, (i, row)| {
-        if i == 0 || i == matrix.len() - 1 {
-            acc
-        } else {
-            acc + row.iter().enumerate().fold(0, |row_acc, (j, v)| {
-                if *v == 0 || j == 0 || j == row.len() - 1 {
-                    row_acc
-                } else {
-                    let count_adjacent = matrix[i - 1][j - 1]
-                        + matrix[i - 1][j]
-                        + matrix[i - 1][j + 1]
-                        + matrix[i][j - 1]
-                        + matrix[i][j + 1]
-                        + matrix[i + 1][j - 1]
-                        + matrix[i + 1][j]
-                        + matrix[i + 1][j + 1];
-                    // println!("i, j : ({:?}, {:?})", i, j);
-                    // println!("count_adjacent {:?}", count_adjacent);
-                    // println!("count_adjacent {:?}", count_adjacent);
-
-                    // println!("{:?} {:?} {:?}", matrix[i - 1][j - 1],matrix[i - 1][j],matrix[i - 1][j + 1]);
-                    // println!("{:?} @ {:?}", matrix[i][j - 1], matrix[i][j + 1]);
-                    // println!("{:?} {:?} {:?}", matrix[i + 1][j - 1], matrix[i + 1][j], matrix[i + 1][j + 1]);
-
-                    if count_adjacent < 4 {
-                        row_acc + 1
-                    } else {
-                        row_acc
-                    }
-                }
-            })
-        }
-    })
+    Ok(Grid::from_rows(rows).expect("char_grid only returns rectangles"))
 }
 
-fn is_removable(matrix: &Vec<Vec<u8>>, i: usize, j: usize) -> bool {
-    if matrix[i][j] == 1 && i > 0 && i < matrix.len() && j > 0 && j < matrix[i].len() {
-        let count_adjacent = matrix[i - 1][j - 1]
-            + matrix[i - 1][j]
-            + matrix[i - 1][j + 1]
-            + matrix[i][j - 1]
-            + matrix[i][j + 1]
-            + matrix[i + 1][j - 1]
-            + matrix[i + 1][j]
-            + matrix[i + 1][j + 1];
-
-        if count_adjacent < 4 { true } else { false }
-    } else {
-        false
-    }
+// A roll can be reached by a forklift when fewer than 4 of the 8 cells around it hold a roll.
+fn is_removable(matrix: &Grid<u8>, pos: Pos) -> bool {
+    matrix[pos] == 1
+        && matrix
+            .around(pos, &ALL_AROUND, Border::Fill(&0))
+            .sum::<u8>()
+            < 4
 }
 
-fn first_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let content = read_input(file_path)?;
-
-    let zp = zero_padding(content);
-    let ca = count_adjacent(zp);
+pub fn first_challenge(content: &Grid<u8>) -> Result<u64, Box<dyn Error>> {
+    let ca = content
+        .positions()
+        .filter(|pos| is_removable(content, *pos))
+        .count();
 
-    Ok(ca)
+    Ok(ca as u64)
 }
 
-fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let content = read_input(file_path)?;
-
-    let zp = zero_padding(content);
-
-    let mut eroded_matrix = zp.clone();
+pub fn second_challenge(content: &Grid<u8>) -> Result<u64, Box<dyn Error>> {
+    let mut eroded_matrix = content.clone();
 
-    let start_count = eroded_matrix.iter().fold(0, |acc, row| {
-        acc + row.iter().fold(0, |row_acc, v| row_acc + *v as u64)
-    });
+    let start_count = eroded_matrix.iter().map(|v| *v as u64).sum::<u64>();
 
-    // println!("rolls at start {:?}", start_count);
+    debug!("Rolls at start: {}", start_count);
 
     let mut curr_count = start_count;
     let mut prev_count = 0;
 
     while curr_count != prev_count {
-        eroded_matrix = eroded_matrix
-            .iter()
-            .enumerate()
-            .map(|(i, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(|(j, v)| {
-                        if is_removable(&eroded_matrix, i, j) {
-                            0
-                        } else {
-                            *v
-                        }
-                    })
-                    .collect()
-            })
+        let removable: Vec<Pos> = eroded_matrix
+            .positions()
+            .filter(|pos| is_removable(&eroded_matrix, *pos))
             .collect();
+        for pos in removable {
+            eroded_matrix[pos] = 0;
+        }
 
         prev_count = curr_count;
-        curr_count = eroded_matrix.iter().fold(0, |acc, row| {
-            acc + row.iter().fold(0, |row_acc, v| row_acc + *v as u64)
-        });
+        curr_count = eroded_matrix.iter().map(|v| *v as u64).sum::<u64>();
 
-        // println!("removed {:?} rolls", prev_count - curr_count);
+        debug!("Removed {} rolls", prev_count - curr_count);
     }
 
     Ok(start_count - curr_count)
 }
 
-pub fn entry() {
-    println!("=== Day 4 ===");
+pub struct Day4;
 
-    let file_path_test = "data/day4_test.txt";
-    let file_path = "data/day4.txt";
-    let iterations = 100;
+impl Solution for Day4 {
+    type Input = Grid<u8>;
+    type Output = u64;
 
-    println!("=== Challenge 1 ===");
-    match first_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    fn day(&self) -> u8 {
+        4
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = first_challenge(file_path);
+    fn name(&self) -> &'static str {
+        "Printing Department"
     }
-    let duration = start.elapsed();
-    match first_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    fn parse(&self, contents: &str, _kind: InputKind) -> Result<Self::Input, ParseError> {
+        read_input(contents)
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
-
-    println!("=== Challenge 2 ===");
-    match second_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        first_challenge(input)
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = second_challenge(file_path);
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        second_challenge(input)
     }
-    let duration = start.elapsed();
-    match second_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../data/day4_test.txt");
+
+    #[test]
+    fn first_challenge_example() {
+        let input = Day4.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day4.part1(&input).unwrap(), 13);
+    }
+
+    #[test]
+    fn second_challenge_example() {
+        let input = Day4.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day4.part2(&input).unwrap(), 43);
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
 }
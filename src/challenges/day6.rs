@@ -1,59 +1,85 @@
 use std::error::Error;
-use std::fs;
 use std::iter::zip;
-use std::num::ParseIntError;
-use std::time::Instant;
 
-#[derive(Debug)]
-enum Operator {
+use super::{InputKind, Solution};
+use crate::error::{ParseError, ParseErrorKind};
+use crate::parse;
+use crate::trace;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
     Add,
     Multiply,
 }
 
-fn read_input(file_path: &str) -> Result<(Vec<Vec<u64>>, Vec<Operator>), Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
+pub type Worksheet = (Vec<Vec<u64>>, Vec<Operator>);
+
+// The same homework read row-wise (part 1) and column-wise (part 2).
+#[derive(Debug)]
+pub struct Homework {
+    pub rows: Worksheet,
+    pub columns: Worksheet,
+}
+
+fn parse_operator(contents: &str, x: &str) -> Result<Operator, ParseError> {
+    match x {
+        "+" => Ok(Operator::Add),
+        "*" => Ok(Operator::Multiply),
+        _ => Err(ParseError::at(contents, x, ParseErrorKind::InvalidOperator)),
+    }
+}
+
+// Splits the worksheet into its number lines and the trailing, parsed operator line.
+fn split_lines(contents: &str) -> Result<(Vec<&str>, &str, Vec<Operator>), ParseError> {
+    let file_lines: Vec<&str> = parse::lines(contents).collect();
 
-    let num_lines: usize = contents
-        .lines()
-        .filter(|line| !line.is_empty())
-        .fold(0, |acc, _| acc + 1);
+    let (operator_line, number_lines) = match file_lines.split_last() {
+        Some(result) => result,
+        None => return Err(ParseError::eof(contents, ParseErrorKind::Empty)),
+    };
+
+    let mut tokens = operator_line
+        .split(" ")
+        .filter(|x| !x.is_empty())
+        .peekable();
+    if tokens.peek().is_none() || tokens.all(|x| x.parse::<u64>().is_ok()) {
+        let kind = ParseErrorKind::MissingSection("operator line");
+        return Err(ParseError::eof(contents, kind));
+    }
+    if number_lines.is_empty() {
+        let kind = ParseErrorKind::MissingSection("number lines");
+        return Err(ParseError::at(contents, operator_line, kind));
+    }
+
+    let operators = operator_line
+        .split(" ")
+        .filter(|x| !x.is_empty())
+        .map(|x| parse_operator(contents, x))
+        .collect::<Result<Vec<Operator>, ParseError>>()?;
+
+    Ok((number_lines.to_vec(), operator_line, operators))
+}
+
+pub fn read_input(contents: &str) -> Result<Worksheet, ParseError> {
+    let (lines, _, operators) = split_lines(contents)?;
 
-    let num_number_lines = num_lines - 1;
     let mut number_lines: Vec<Vec<u64>> = vec![];
-    let mut operator_lines: Vec<Operator> = vec![];
-
-    for (line_num, line) in contents.lines().filter(|line| !line.is_empty()).enumerate() {
-        if line_num < num_number_lines {
-            let l: Result<Vec<u64>, ParseIntError> = line
-                .split(" ")
-                .into_iter()
-                .filter(|line| !line.is_empty())
-                .map(|x| x.parse::<u64>())
-                .collect();
-            number_lines.push(l.unwrap());
-        } else {
-            let l: Result<Vec<Operator>, Box<dyn Error>> = line
-                .split(" ")
-                .into_iter()
-                .filter(|line| !line.is_empty())
-                .map(|x| match x {
-                    "+" => Ok(Operator::Add),
-                    "*" => Ok(Operator::Multiply),
-                    _ => Err(format!("Invalid operator: {}", x).into()),
-                })
-                .collect();
-            operator_lines = l.unwrap();
+
+    for line in lines.iter() {
+        let l: Vec<u64> = parse::numbers(contents, line, ' ')?;
+
+        if l.len() != operators.len() {
+            let kind = ParseErrorKind::RaggedRow {
+                expected: operators.len(),
+                found: l.len(),
+            };
+            return Err(ParseError::at(contents, line, kind));
         }
-    }
 
-    let expected_len = operator_lines.len();
-    assert!(
-        number_lines.iter().all(|v| v.len() == expected_len),
-        "Not all vectors have length {}",
-        expected_len
-    );
+        number_lines.push(l);
+    }
 
-    let mut transposed_numbers = vec![vec![0; num_number_lines]; expected_len];
+    let mut transposed_numbers = vec![vec![0; number_lines.len()]; operators.len()];
 
     for (i, number_line) in number_lines.iter().enumerate() {
         for (j, v) in number_line.iter().enumerate() {
@@ -61,59 +87,49 @@ fn read_input(file_path: &str) -> Result<(Vec<Vec<u64>>, Vec<Operator>), Box<dyn
         }
     }
 
-    Ok((transposed_numbers, operator_lines))
+    Ok((transposed_numbers, operators))
 }
 
-fn read_input_2(file_path: &str) -> Result<(Vec<Vec<u64>>, Vec<Operator>), Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
-
-    let file_lines: Vec<&str> = contents
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|x| x)
-        .collect();
-    let num_lines = file_lines.len();
+pub fn read_input_2(contents: &str) -> Result<Worksheet, ParseError> {
+    let (lines, operator_line, operators) = split_lines(contents)?;
 
-    let number_lines: Vec<Vec<&str>> = file_lines
-        .get(..num_lines - 1)
-        .unwrap()
+    // Digits, or None for the spaces between them.
+    let number_lines: Vec<Vec<Option<u8>>> = lines
         .iter()
         .map(|line| {
-            line.split("")
-                .filter(|line| !line.is_empty())
-                .collect::<Vec<&str>>()
-        })
-        .collect();
-    let operators = file_lines
-        .get(num_lines - 1)
-        .unwrap()
-        .split(" ")
-        .into_iter()
-        .filter(|line| !line.is_empty())
-        .map(|x| match x {
-            "+" => Ok(Operator::Add),
-            "*" => Ok(Operator::Multiply),
-            _ => Err(format!("Invalid operator: {}", x).into()),
+            parse::char_row(contents, line, |c| match c {
+                ' ' => Some(None),
+                _ => c.to_digit(10).map(|digit| Some(digit as u8)),
+            })
         })
-        .collect::<Result<Vec<Operator>, Box<dyn Error>>>()
-        .unwrap();
+        .collect::<Result<_, ParseError>>()?;
+
+    // Numbers are read top to bottom per column, so every line has to be equally wide.
+    let width = number_lines[0].len();
+    for (line, number_vec) in zip(&lines, &number_lines) {
+        if number_vec.len() != width {
+            let kind = ParseErrorKind::RaggedRow {
+                expected: width,
+                found: number_vec.len(),
+            };
+            return Err(ParseError::at(contents, line, kind));
+        }
+    }
 
     let mut numbers: Vec<Vec<u64>> = vec![];
 
     let mut number_group: Vec<u64> = vec![];
-    for (i, _) in number_lines[0].iter().enumerate() {
+    for i in 0..width {
         let n = number_lines
             .iter()
-            .map(|number_vec| {
-                let candidate = number_vec[i];
-                if candidate == " " { "" } else { candidate }
-            })
-            .collect::<String>()
-            .parse::<u64>();
+            .filter_map(|number_vec| number_vec[i])
+            .fold(None, |acc, digit| {
+                Some(acc.unwrap_or(0) * 10 + digit as u64)
+            });
 
         match n {
-            Ok(result) => number_group.push(result),
-            Err(_) => {
+            Some(result) => number_group.push(result),
+            None => {
                 numbers.push(number_group);
 
                 number_group = vec![];
@@ -123,21 +139,27 @@ fn read_input_2(file_path: &str) -> Result<(Vec<Vec<u64>>, Vec<Operator>), Box<d
 
     numbers.push(number_group);
 
-    // println!("numbers: {:?}", numbers);
-    // println!("operators: {:?}", operators);
+    if numbers.len() != operators.len() {
+        let kind = ParseErrorKind::FieldCount {
+            expected: numbers.len(),
+            found: operators.len(),
+        };
+        return Err(ParseError::at(contents, operator_line, kind));
+    }
+
+    trace!("Numbers: {:?}", numbers);
+    trace!("Operators: {:?}", operators);
 
     Ok((numbers, operators))
 }
 
-fn first_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let content = read_input(file_path)?;
-
+pub fn first_challenge(content: &Worksheet) -> Result<u64, Box<dyn Error>> {
     let mut grand_sum = 0;
 
-    for (numbers, operator) in zip(content.0, content.1) {
+    for (numbers, operator) in zip(&content.0, &content.1) {
         let result = match operator {
-            Operator::Add => numbers.iter().fold(0, |acc, x| acc + x),
-            Operator::Multiply => numbers.iter().fold(1, |acc, x| acc * x),
+            Operator::Add => numbers.iter().sum::<u64>(),
+            Operator::Multiply => numbers.iter().product::<u64>(),
         };
 
         grand_sum += result;
@@ -146,15 +168,13 @@ fn first_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
     Ok(grand_sum)
 }
 
-fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let content = read_input_2(file_path)?;
-
+pub fn second_challenge(content: &Worksheet) -> Result<u64, Box<dyn Error>> {
     let mut grand_sum = 0;
 
-    for (numbers, operator) in zip(content.0, content.1) {
+    for (numbers, operator) in zip(&content.0, &content.1) {
         let result = match operator {
-            Operator::Add => numbers.iter().fold(0, |acc, x| acc + x),
-            Operator::Multiply => numbers.iter().fold(1, |acc, x| acc * x),
+            Operator::Add => numbers.iter().sum::<u64>(),
+            Operator::Multiply => numbers.iter().product::<u64>(),
         };
 
         grand_sum += result;
@@ -163,52 +183,101 @@ fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
     Ok(grand_sum)
 }
 
-pub fn entry() {
-    println!("=== Day 6 ===");
+pub struct Day6;
 
-    let file_path_test = "data/day6_test.txt";
-    let file_path = "data/day6.txt";
-    let iterations = 100;
+impl Solution for Day6 {
+    type Input = Homework;
+    type Output = u64;
 
-    println!("=== Challenge 1 ===");
-    match first_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    fn day(&self) -> u8 {
+        6
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = first_challenge(file_path);
+    fn name(&self) -> &'static str {
+        "Trash Compactor"
     }
-    let duration = start.elapsed();
-    match first_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    fn parse(&self, contents: &str, _kind: InputKind) -> Result<Self::Input, ParseError> {
+        Ok(Homework {
+            rows: read_input(contents)?,
+            columns: read_input_2(contents)?,
+        })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        first_challenge(&input.rows)
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
 
-    println!("=== Challenge 2 ===");
-    match second_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        second_challenge(&input.columns)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../data/day6_test.txt");
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = second_challenge(file_path);
+    #[test]
+    fn first_challenge_example() {
+        let input = Day6.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day6.part1(&input).unwrap(), 4277556);
     }
-    let duration = start.elapsed();
-    match second_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    #[test]
+    fn second_challenge_example() {
+        let input = Day6.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day6.part2(&input).unwrap(), 3263827);
+    }
+
+    #[test]
+    fn empty_input() {
+        for contents in ["", "\n\n"] {
+            let e = Day6.parse(contents, InputKind::Real).err().unwrap();
+            assert_eq!(e.kind, ParseErrorKind::Empty);
+        }
+    }
+
+    #[test]
+    fn missing_operator_line() {
+        let e = Day6.parse("1 2\n3 4\n", InputKind::Real).err().unwrap();
+        assert_eq!(e.kind, ParseErrorKind::MissingSection("operator line"));
+
+        let e = Day6.parse("+ *\n", InputKind::Real).err().unwrap();
+        assert_eq!(e.kind, ParseErrorKind::MissingSection("number lines"));
+    }
+
+    #[test]
+    fn unknown_operator() {
+        let e = Day6
+            .parse("1 2\n3 4\n+ -\n", InputKind::Real)
+            .err()
+            .unwrap();
+        assert_eq!(e.kind, ParseErrorKind::InvalidOperator);
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 3, "-"));
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let e = read_input("1 2 3\n3 4\n+ * +\n").err().unwrap();
+        assert_eq!(
+            e.kind,
+            ParseErrorKind::RaggedRow {
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(e.line, 2);
+
+        let e = read_input_2("12 3\n3 4\n+ *\n").err().unwrap();
+        assert_eq!(
+            e.kind,
+            ParseErrorKind::RaggedRow {
+                expected: 4,
+                found: 3
+            }
+        );
+        assert_eq!(e.line, 2);
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
 }
@@ -1,10 +1,14 @@
 use std::error::Error;
-use std::fs;
 use std::ops::Add;
-use std::time::Instant;
+
+use super::{InputKind, Solution};
+use crate::error::ParseError;
+use crate::grid::Grid;
+use crate::parse;
+use crate::{debug, trace};
 
 #[derive(Debug, Clone, Copy)]
-enum State {
+pub enum State {
     // Empty,
     Source,
     Splitter,
@@ -22,125 +26,100 @@ impl Add for State {
     }
 }
 
-fn read_input(file_path: &str) -> Result<Vec<Vec<State>>, Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
-
-    let tachyon_manifold: Result<Vec<Vec<State>>, Box<dyn Error>> = contents
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|row| {
-            row.chars()
-                .map(|x| match x {
-                    '.' => Ok(State::Beam(0)),
-                    'S' => Ok(State::Source),
-                    '^' => Ok(State::Splitter),
-                    '|' => Ok(State::Beam(1)),
-                    _ => Err(format!("Invalid operator: {}", x).into()),
-                })
-                .collect()
-        })
-        .collect();
-
-    tachyon_manifold
-}
-
-fn _print_manifold(manifold: Vec<Vec<State>>) {
-    manifold.iter().for_each(|row| {
-        let c: String = row
-            .iter()
-            .map(|x| match x {
-                State::Beam(0) => '.',
-                State::Beam(_) => '|',
-                State::Splitter => '^',
-                State::Source => 'S',
-            })
-            .collect();
-        println!("{}", c);
-    })
+impl State {
+    pub fn symbol(&self) -> char {
+        match self {
+            State::Beam(0) => '.',
+            State::Beam(_) => '|',
+            State::Splitter => '^',
+            State::Source => 'S',
+        }
+    }
 }
 
-fn simulate_manifold(manifold: Vec<Vec<State>>) -> Result<(Vec<Vec<State>>, u64), Box<dyn Error>> {
-    let mut split_count = 0;
-    let max_w = match manifold.first() {
-        Some(result) => result.len(),
-        None => return Err(format!("Error fetching first row!").into()),
-    };
-
-    let mut simulated_manifold: Vec<Vec<State>> = vec![];
-
-    for (i, row) in manifold.iter().enumerate() {
-        if i == 0 {
-            simulated_manifold.push(row.clone());
-        } else {
-            let prev_row = match simulated_manifold.last() {
-                Some(result) => result,
-                None => return Err(format!("Error fetching last row!").into()),
-            };
+pub type Manifold = Grid<State>;
 
+pub fn read_input(contents: &str) -> Result<Manifold, ParseError> {
+    let rows = parse::char_grid(contents, contents, |x| match x {
+        '.' => Some(State::Beam(0)),
+        'S' => Some(State::Source),
+        '^' => Some(State::Splitter),
+        '|' => Some(State::Beam(1)),
+        _ => None,
+    })?;
 
-            let mut new_row: Vec<State> = row.clone();
-
-            for (j, x) in row.iter().enumerate() {
-                // let beam_prev = beam_indices.contains(&i);
+    Ok(Grid::from_rows(rows).expect("char_grid only returns rectangles"))
+}
 
-                match x {
-                    State::Beam(_) => match prev_row[j] {
-                        State::Beam(level) => {
-                            new_row[j] = new_row[j] + State::Beam(level);
+fn simulate_manifold(manifold: &Manifold) -> Result<(Manifold, u64), Box<dyn Error>> {
+    let mut split_count = 0;
+    if manifold.height() == 0 {
+        return Err("Error fetching first row!".to_string().into());
+    }
+    let max_w = manifold.width();
+
+    // Every row starts out as in the input and collects the beams of the row above.
+    let mut simulated_manifold = manifold.clone();
+
+    for i in 1..manifold.height() {
+        for j in 0..max_w {
+            // let beam_prev = beam_indices.contains(&i);
+            let prev = simulated_manifold[(i - 1, j)];
+
+            match manifold[(i, j)] {
+                State::Beam(_) => match prev {
+                    State::Beam(level) => {
+                        simulated_manifold[(i, j)] =
+                            simulated_manifold[(i, j)] + State::Beam(level);
+                    }
+                    State::Source => simulated_manifold[(i, j)] = State::Beam(1),
+                    _ => {}
+                },
+                State::Splitter => match prev {
+                    State::Beam(0) => {}
+                    State::Beam(level) => {
+                        split_count += 1;
+                        trace!("Split on ({}, {})", i, j);
+                        if j > 0 {
+                            simulated_manifold[(i, j - 1)] =
+                                simulated_manifold[(i, j - 1)] + State::Beam(level)
                         }
-                        State::Source => new_row[j] = State::Beam(1),
-                        _ => {}
-                    },
-                    State::Splitter => match prev_row[j] {
-                        State::Beam(0) => {}
-                        State::Beam(level) => {
-                            split_count += 1;
-                            // println!("split on ({:?}, {:?})", i, j);
-                            if j > 0 {
-                                new_row[j - 1] = new_row[j - 1] + State::Beam(level)
-                            }
-                            if j < max_w - 1 {
-                                new_row[j + 1] = new_row[j + 1] + State::Beam(level)
-                            }
+                        if j < max_w - 1 {
+                            simulated_manifold[(i, j + 1)] =
+                                simulated_manifold[(i, j + 1)] + State::Beam(level)
                         }
-                        _ => {}
-                    },
+                    }
                     _ => {}
-                }
+                },
+                _ => {}
             }
-
-            simulated_manifold.push(new_row);
         }
     }
 
-    // println!("{:?}", simulated_manifold);
+    debug!("{} splits", split_count);
+    trace!("Manifold:\n{}", simulated_manifold.render(State::symbol));
 
     Ok((simulated_manifold, split_count))
 }
 
-fn first_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let content = read_input(file_path)?;
-
+pub fn first_challenge(content: &Manifold) -> Result<u64, Box<dyn Error>> {
     let simulated_manifold = simulate_manifold(content)?;
-    // print_manifold(simulated_manifold.0);
 
     Ok(simulated_manifold.1)
 }
 
-fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let content = read_input(file_path)?;
-
+pub fn second_challenge(content: &Manifold) -> Result<u64, Box<dyn Error>> {
     let simulated_manifold = simulate_manifold(content)?;
 
-    let last_row = match simulated_manifold.0.last() {
-                Some(result) => result,
-                None => return Err(format!("Error fetching last row!").into()),
-            };
+    let last_row = match simulated_manifold.0.rows().last() {
+        Some(result) => result,
+        None => return Err("Error fetching last row!".to_string().into()),
+    };
 
     let total_paths = last_row.iter().fold(0, |count, v| {
         let c = match v {
             State::Beam(level) => *level,
-            _ => 0u64
+            _ => 0u64,
         };
 
         count + c
@@ -149,52 +128,66 @@ fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
     Ok(total_paths)
 }
 
-pub fn entry() {
-    println!("=== Day 7 ===");
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Manifold;
+    type Output = u64;
+
+    fn day(&self) -> u8 {
+        7
+    }
 
-    let file_path_test = "data/day7_test.txt";
-    let file_path = "data/day7.txt";
-    let iterations = 100;
+    fn name(&self) -> &'static str {
+        "Laboratories"
+    }
 
-    println!("=== Challenge 1 ===");
-    match first_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    fn parse(&self, contents: &str, _kind: InputKind) -> Result<Self::Input, ParseError> {
+        read_input(contents)
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = first_challenge(file_path);
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        first_challenge(input)
     }
-    let duration = start.elapsed();
-    match first_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        second_challenge(input)
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
-
-    println!("=== Challenge 2 ===");
-    match second_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../data/day7_test.txt");
+
+    #[test]
+    fn first_challenge_example() {
+        let input = Day7.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day7.part1(&input).unwrap(), 21);
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = second_challenge(file_path);
+    #[test]
+    fn second_challenge_example() {
+        let input = Day7.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day7.part2(&input).unwrap(), 40);
     }
-    let duration = start.elapsed();
-    match second_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    #[test]
+    fn render_beams() {
+        let input = Day7.parse(EXAMPLE, InputKind::Example).unwrap();
+        let (simulated, _) = simulate_manifold(&input).unwrap();
+        let rendered = simulated.render(State::symbol);
+
+        let top: Vec<&str> = rendered.lines().take(4).collect();
+        assert_eq!(
+            top,
+            [
+                ".......S.......",
+                ".......|.......",
+                "......|^|......",
+                "......|.|......"
+            ]
+        );
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
 }
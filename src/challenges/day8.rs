@@ -1,28 +1,35 @@
 use std::collections::HashSet;
 use std::error::Error;
-use std::fs;
-use std::time::Instant;
 
-fn read_input(file_path: &str) -> Result<Vec<(u32, u32, u32)>, Box<dyn Error>> {
-    let contents = fs::read_to_string(file_path)?;
+use super::{InputKind, Solution};
+use crate::error::ParseError;
+use crate::parse;
+use crate::{debug, trace};
 
-    let vector_list: Result<Vec<(u32, u32, u32)>, _> = contents
-        .lines()
-        .filter(|line| !line.is_empty())
+pub type JunctionBox = (u32, u32, u32);
+
+#[derive(Debug)]
+pub struct Playground {
+    pub boxes: Vec<JunctionBox>,
+    // The example only connects the 10 closest pairs, the real input 1000.
+    pub connections: usize,
+}
+
+pub fn read_input(contents: &str) -> Result<Vec<JunctionBox>, ParseError> {
+    parse::lines(contents)
         .map(|row| {
-            let parsed_vec = row
-                .split(",")
-                .map(|x| x.parse::<u32>())
-                .collect::<Result<Vec<u32>, _>>()?;
-
-            match parsed_vec.len() {
-                3 => Ok((parsed_vec[0], parsed_vec[1], parsed_vec[2])),
-                len => Err(format!("Invalid vector length: {}", len).into()),
-            }
+            let [x, y, z] = parse::fields(contents, row, ',')?;
+            Ok((x, y, z))
         })
-        .collect();
+        .collect()
+}
+
+fn distance(box_1: &JunctionBox, box_2: &JunctionBox) -> f32 {
+    let squared = (box_1.0.abs_diff(box_2.0) as u64).pow(2)
+        + (box_1.1.abs_diff(box_2.1) as u64).pow(2)
+        + (box_1.2.abs_diff(box_2.2) as u64).pow(2);
 
-    vector_list
+    (squared as f32).sqrt()
 }
 
 fn merge_connections(connections: Vec<HashSet<usize>>) -> Vec<HashSet<usize>> {
@@ -41,7 +48,7 @@ fn merge_connections(connections: Vec<HashSet<usize>>) -> Vec<HashSet<usize>> {
 
                 for (i, set) in merged_connection_groups.iter().enumerate() {
                     let intersection: HashSet<_> = set.intersection(curr_set).collect();
-                    if intersection.len() > 0 {
+                    if !intersection.is_empty() {
                         merged_connection_groups[i] = set.union(curr_set).copied().collect();
                         found_connection_group = true;
                         break;
@@ -64,10 +71,11 @@ fn merge_connections(connections: Vec<HashSet<usize>>) -> Vec<HashSet<usize>> {
     new_connection_groups
 }
 
-fn first_challenge(file_path: &str, connections: usize) -> Result<u64, Box<dyn Error>> {
-    let vector_list = read_input(file_path)?;
-
-    // println!("vectors:\n{:?}", vector_list);
+pub fn first_challenge(
+    vector_list: &[JunctionBox],
+    connections: usize,
+) -> Result<u64, Box<dyn Error>> {
+    trace!("Boxes: {:?}", vector_list);
     let mut smallest_distances: Vec<(HashSet<usize>, f32)> =
         vec![(HashSet::new(), f32::MAX); connections];
 
@@ -77,17 +85,14 @@ fn first_challenge(file_path: &str, connections: usize) -> Result<u64, Box<dyn E
                 continue;
             }
 
-            let distance = (((box_1.0 - box_2.0).pow(2)
-                + (box_1.1 - box_2.1).pow(2)
-                + (box_1.2 - box_2.2).pow(2)) as f32)
-                .sqrt();
+            let distance = distance(box_1, box_2);
 
             let last = match smallest_distances.last() {
                 Some(result) => result,
                 None => {
-                    return Err(
-                        format!("Error fetching last item in smallest distances vector!").into(),
-                    );
+                    return Err("Error fetching last item in smallest distances vector!"
+                        .to_string()
+                        .into());
                 }
             };
 
@@ -99,8 +104,8 @@ fn first_challenge(file_path: &str, connections: usize) -> Result<u64, Box<dyn E
             }
         }
     }
-    // println!("smallest_distances: {:?}", smallest_distances.len());
-    // println!("smallest_distances: {:?}", smallest_distances);
+    debug!("{} closest pairs", smallest_distances.len());
+    trace!("Closest pairs: {:?}", smallest_distances);
 
     let merged_connection_groups = merge_connections(
         smallest_distances
@@ -108,7 +113,13 @@ fn first_challenge(file_path: &str, connections: usize) -> Result<u64, Box<dyn E
             .map(|x| x.0.clone())
             .collect::<Vec<HashSet<usize>>>(),
     );
-    // println!("merged_connection_groups: {:?}", merged_connection_groups);
+    debug!(
+        "Circuit sizes: {:?}",
+        merged_connection_groups
+            .iter()
+            .map(|s| s.len())
+            .collect::<Vec<usize>>()
+    );
 
     let product = merged_connection_groups[..3]
         .iter()
@@ -117,9 +128,7 @@ fn first_challenge(file_path: &str, connections: usize) -> Result<u64, Box<dyn E
     Ok(product as u64)
 }
 
-fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
-    let vector_list = read_input(file_path)?;
-
+pub fn second_challenge(vector_list: &[JunctionBox]) -> Result<u64, Box<dyn Error>> {
     let len = vector_list.len();
 
     let mut smallest_distances: Vec<(HashSet<usize>, f32)> = vec![];
@@ -130,10 +139,7 @@ fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
                 continue;
             }
 
-            let distance = (((box_1.0 - box_2.0).pow(2)
-                + (box_1.1 - box_2.1).pow(2)
-                + (box_1.2 - box_2.2).pow(2)) as f32)
-                .sqrt();
+            let distance = distance(box_1, box_2);
 
             smallest_distances.push((HashSet::from([i, j]), distance));
         }
@@ -150,7 +156,7 @@ fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
         }
     }
 
-    // println!("last_connection {:?}", acc_set);
+    debug!("Last connection: {:?}", acc_set);
 
     let product: u64 = acc_set
         .iter()
@@ -159,52 +165,56 @@ fn second_challenge(file_path: &str) -> Result<u64, Box<dyn Error>> {
     Ok(product)
 }
 
-pub fn entry() {
-    println!("=== Day 8 ===");
+pub struct Day8;
 
-    let file_path_test = "data/day8_test.txt";
-    let file_path = "data/day8.txt";
-    let iterations = 100;
+impl Solution for Day8 {
+    type Input = Playground;
+    type Output = u64;
 
-    println!("=== Challenge 1 ===");
-    match first_challenge(file_path_test, 10) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    fn day(&self) -> u8 {
+        8
     }
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = first_challenge(file_path, 1000);
+    fn name(&self) -> &'static str {
+        "Playground"
     }
-    let duration = start.elapsed();
-    match first_challenge(file_path, 1000) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    fn parse(&self, contents: &str, kind: InputKind) -> Result<Self::Input, ParseError> {
+        let connections = match kind {
+            InputKind::Example => 10,
+            InputKind::Real => 1000,
+        };
+
+        Ok(Playground {
+            boxes: read_input(contents)?,
+            connections,
+        })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        first_challenge(&input.boxes, input.connections)
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
 
-    println!("=== Challenge 2 ===");
-    match second_challenge(file_path_test) {
-        Ok(result) => println!("Test result: {}", result),
-        Err(e) => eprintln!("Error in test: {}", e),
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        second_challenge(&input.boxes)
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../../data/day8_test.txt");
 
-    let start = Instant::now();
-    for _ in 0..iterations {
-        let _ = second_challenge(file_path);
+    #[test]
+    fn first_challenge_example() {
+        let input = Day8.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day8.part1(&input).unwrap(), 40);
     }
-    let duration = start.elapsed();
-    match second_challenge(file_path) {
-        Ok(result) => println!("Result: {}", result),
-        Err(e) => eprintln!("Error: {}", e),
+
+    #[test]
+    fn second_challenge_example() {
+        let input = Day8.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day8.part2(&input).unwrap(), 25272);
     }
-    println!(
-        "Average time on {:?} iterations: {:?}",
-        iterations,
-        duration / iterations
-    );
 }
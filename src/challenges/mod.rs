@@ -1,3 +1,10 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::error::ParseError;
+use crate::input::InputSource;
+
 pub mod day1;
 pub mod day2;
 pub mod day3;
@@ -5,4 +12,128 @@ pub mod day4;
 pub mod day5;
 pub mod day6;
 pub mod day7;
-pub mod day8;
\ No newline at end of file
+pub mod day8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    Example,
+    Real,
+}
+
+pub trait Solution {
+    type Input;
+    type Output: Display;
+
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn parse(&self, contents: &str, kind: InputKind) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>>;
+
+    // Anything worth knowing about an input besides the answers. Most days have nothing.
+    fn analyse(&self, _input: &Self::Input) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(None)
+    }
+
+    fn load(&self, source: &InputSource, kind: InputKind) -> Result<Self::Input, Box<dyn Error>> {
+        let contents = source.read()?;
+        let input = self
+            .parse(&contents, kind)
+            .map_err(|e| e.with_file(&source.to_string()))?;
+
+        Ok(input)
+    }
+}
+
+// Object safe view of a `Solution`, so days with different input types can share one registry.
+// The parsed input is type-erased so it can be parsed once and handed to both parts. Days and
+// their inputs are shared between threads when running in parallel.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn name(&self) -> &'static str;
+    fn parse(&self, contents: &str, kind: InputKind) -> Result<Box<dyn Any + Send>, ParseError>;
+    fn solve(&self, input: &dyn Any, part: u8) -> Result<String, Box<dyn Error>>;
+    fn analyse(&self, input: &dyn Any) -> Result<Option<String>, Box<dyn Error>>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Input: Send + 'static,
+{
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn name(&self) -> &'static str {
+        Solution::name(self)
+    }
+
+    fn parse(&self, contents: &str, kind: InputKind) -> Result<Box<dyn Any + Send>, ParseError> {
+        let input = Solution::parse(self, contents, kind)?;
+        Ok(Box::new(input))
+    }
+
+    fn solve(&self, input: &dyn Any, part: u8) -> Result<String, Box<dyn Error>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .ok_or("Input was parsed by a different day")?;
+
+        let result = match part {
+            1 => self.part1(input)?,
+            2 => self.part2(input)?,
+            _ => return Err(format!("Invalid part: {}", part).into()),
+        };
+
+        Ok(result.to_string())
+    }
+
+    fn analyse(&self, input: &dyn Any) -> Result<Option<String>, Box<dyn Error>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .ok_or("Input was parsed by a different day")?;
+
+        Solution::analyse(self, input)
+    }
+}
+
+pub fn registry() -> Vec<&'static dyn DynSolution> {
+    vec![
+        &day1::Day1,
+        &day2::Day2,
+        &day3::Day3,
+        &day4::Day4,
+        &day5::Day5,
+        &day6::Day6,
+        &day7::Day7,
+        &day8::Day8,
+    ]
+}
+
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+    registry()
+        .into_iter()
+        .find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn load_from_source() {
+        let source = InputSource::Text("R5\nL2\n".to_string());
+        let input = day1::Day1.load(&source, InputKind::Example).unwrap();
+        assert_eq!(input, [5, -2]);
+
+        // Parse errors are located in the source they come from.
+        let source = InputSource::Text("R5\nX2\n".to_string());
+        let e = day1::Day1.load(&source, InputKind::Real).unwrap_err();
+        assert_eq!(e.to_string(), "<memory>:2:1: Invalid direction: X2");
+
+        let source = InputSource::Path(PathBuf::from("missing/day1.txt"));
+        let e = day1::Day1.load(&source, InputKind::Real).unwrap_err();
+        assert!(e.to_string().starts_with("missing/day1.txt: "), "{}", e);
+    }
+}
@@ -0,0 +1,793 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+
+use crate::fetch;
+use crate::input::{self, InputSource};
+use crate::output::Format;
+use crate::trace::Filter;
+
+pub const USAGE: &str = "\
+Usage: aoc25 <command> [options]
+
+Commands:
+    run <day|all>    Run the example and real input of a day, or of every day
+    new-day <day>    Generate and register the module of a new day, with empty input files
+    fetch <day>      Download the input of a day to data/dayN.txt, unless it is already there
+    watch <day>      Run a day again whenever its input, example or answers change
+    analyse <day>    Print what the day can tell about its real input besides the answers
+    submit <day> <part>
+                     Submit the answer of a part and record the verdict in data/dayN_answers.txt
+    help             Print this message
+
+Options for run:
+    --part <1|2>        Only run the given part
+    --input <path>      Read the real input from <path> instead of data/dayN.txt, - for stdin
+    --example <path>    Read the example input from <path> instead of data/dayN_test.txt, - for stdin
+    --answers <path>    Verify answers against <path> instead of data/dayN_answers.txt
+    --data-dir <path>   Look for the default files in <path> instead of data/ ($AOC25_DATA_DIR)
+    --no-bench          Only compute the answers, skip benchmarking the real input
+    --format <format>   Print the results as text (default), json or csv
+    --jobs <n>          Compute the answers of up to <n> days at once, 0 for one per CPU;
+                        benchmarks still run one day at a time
+    --baseline <path>   Compare the benchmarks against the baseline saved in <path>
+    --save-baseline <path>
+                        Save the benchmarks to <path>, replacing only the days that were run
+    --fail-on-regression
+                        Exit with an error if a benchmark regressed against --baseline
+    --trace <targets>   Print the traces of the solvers to stderr, like day5 or day3=trace,all=info;
+                        levels are info, debug (default) and trace ($AOC25_TRACE)
+
+Options for new-day:
+    --name <name>       Title of the puzzle
+    --root <path>       Generate into the crate at <path> instead of this one
+    --data-dir <path>   Create the input files in <path> instead of data/ of the crate
+
+Options for fetch:
+    --data-dir <path>   Save the input in <path> instead of data/ ($AOC25_DATA_DIR)
+    --base-url <url>    Puzzle site to download from ($AOC25_BASE_URL)
+    --session-file <path>
+                        Read the session token from <path> instead of ~/.config/aoc25/session,
+                        $AOC25_SESSION takes precedence over both
+
+Options for submit:
+    --answer <answer>   Submit <answer> instead of computing it
+    --input <path>      Compute the answer from <path> instead of data/dayN.txt, - for stdin
+    --wait              Sleep through cooldowns instead of giving up
+    --data-dir, --base-url, --session-file
+                        As for fetch
+
+Options for watch:
+    --part, --input, --example, --answers, --data-dir, --trace
+                        As for run
+    --interval <ms>     Check for changes every <ms> milliseconds instead of 500
+    --rebuild           Also watch the sources, rebuild and run with the new binary
+
+Options for analyse:
+    --input <path>      Analyse <path> instead of data/dayN.txt, - for stdin
+    --data-dir <path>   As for run
+
+Inputs of day 1 starting with the line `# syntax: extended` also take rotations written as +n
+and -n, repeats like 3xR45 and # comments.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DaySelection {
+    All,
+    Day(u8),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RunConfig {
+    pub part: Option<u8>,
+    pub input: Option<InputSource>,
+    pub example: Option<InputSource>,
+    pub answers: Option<PathBuf>,
+    pub data_dir: Option<PathBuf>,
+    pub skip_bench: bool,
+    pub format: Format,
+    pub jobs: Option<usize>,
+    pub baseline: Option<PathBuf>,
+    pub save_baseline: Option<PathBuf>,
+    pub fail_on_regression: bool,
+    pub trace: Option<Filter>,
+}
+
+impl RunConfig {
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    // Sequential unless asked otherwise.
+    pub fn jobs(&self) -> usize {
+        match self.jobs {
+            None => 1,
+            Some(0) => thread::available_parallelism().map_or(1, |n| n.get()),
+            Some(jobs) => jobs,
+        }
+    }
+
+    pub fn data_dir(&self) -> PathBuf {
+        self.data_dir
+            .clone()
+            .unwrap_or_else(input::default_data_dir)
+    }
+
+    pub fn input_source(&self, day: u8) -> InputSource {
+        self.input
+            .clone()
+            .unwrap_or_else(|| InputSource::Path(self.data_dir().join(format!("day{}.txt", day))))
+    }
+
+    pub fn example_source(&self, day: u8) -> InputSource {
+        self.example.clone().unwrap_or_else(|| {
+            InputSource::Path(self.data_dir().join(format!("day{}_test.txt", day)))
+        })
+    }
+
+    // The default answers only belong to the default input, a custom input is unverified
+    // unless an answers file is given with it.
+    pub fn answers_path(&self, day: u8) -> Option<PathBuf> {
+        match (&self.answers, &self.input) {
+            (Some(answers), _) => Some(answers.clone()),
+            (None, Some(_)) => None,
+            (None, None) => Some(self.data_dir().join(format!("day{}_answers.txt", day))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewDayConfig {
+    pub day: u8,
+    pub name: String,
+    pub root: PathBuf,
+    pub data_dir: Option<PathBuf>,
+}
+
+impl NewDayConfig {
+    pub fn data_dir(&self) -> PathBuf {
+        self.data_dir
+            .clone()
+            .unwrap_or_else(|| self.root.join("data"))
+    }
+}
+
+// Where the puzzle site is and how to log in to it.
+#[derive(Debug, Clone, Default)]
+pub struct SiteConfig {
+    pub data_dir: Option<PathBuf>,
+    pub base_url: Option<String>,
+    pub session_file: Option<PathBuf>,
+}
+
+impl SiteConfig {
+    pub fn data_dir(&self) -> PathBuf {
+        self.data_dir
+            .clone()
+            .unwrap_or_else(input::default_data_dir)
+    }
+
+    pub fn base_url(&self) -> String {
+        self.base_url
+            .clone()
+            .unwrap_or_else(fetch::default_base_url)
+    }
+
+    pub fn session_file(&self) -> Option<PathBuf> {
+        self.session_file
+            .clone()
+            .or_else(fetch::default_session_file)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FetchConfig {
+    pub day: u8,
+    pub site: SiteConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmitConfig {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub input: Option<InputSource>,
+    pub wait: bool,
+    pub site: SiteConfig,
+}
+
+impl SubmitConfig {
+    pub fn input_source(&self) -> InputSource {
+        self.input.clone().unwrap_or_else(|| {
+            InputSource::Path(self.site.data_dir().join(format!("day{}.txt", self.day)))
+        })
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.site
+            .data_dir()
+            .join(format!("day{}_answers.txt", self.day))
+    }
+
+    pub fn log_path(&self) -> PathBuf {
+        self.site
+            .data_dir()
+            .join(format!("day{}_submissions.txt", self.day))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchConfig {
+    pub day: u8,
+    pub run: RunConfig,
+    pub interval: Duration,
+    // Also watch the sources and rebuild before every run.
+    pub rebuild: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AnalyseConfig {
+    pub day: u8,
+    pub input: Option<InputSource>,
+    pub data_dir: Option<PathBuf>,
+}
+
+impl AnalyseConfig {
+    pub fn input_source(&self) -> InputSource {
+        self.input.clone().unwrap_or_else(|| {
+            let data_dir = self
+                .data_dir
+                .clone()
+                .unwrap_or_else(input::default_data_dir);
+            InputSource::Path(data_dir.join(format!("day{}.txt", self.day)))
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum Command {
+    Run(DaySelection, RunConfig),
+    NewDay(NewDayConfig),
+    Fetch(FetchConfig),
+    Submit(SubmitConfig),
+    Watch(WatchConfig),
+    Analyse(AnalyseConfig),
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    MissingDay,
+    MissingPart,
+    InvalidDay(String),
+    DayExists(u8),
+    InvalidPart(String),
+    InvalidFormat(String),
+    InvalidJobs(String),
+    InvalidInterval(String),
+    InvalidTrace(String),
+    MissingValue(String),
+    UnknownOption(String),
+    UnexpectedArgument(String),
+    PathWithAll(String),
+    StdinTwice,
+    Conflict(&'static str, &'static str),
+    Requires(&'static str, &'static str),
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::MissingCommand => write!(f, "No command given"),
+            CliError::UnknownCommand(c) => write!(f, "Unknown command: {}", c),
+            CliError::MissingDay => write!(f, "No day given"),
+            CliError::MissingPart => write!(f, "No part given"),
+            CliError::InvalidDay(d) => write!(f, "Invalid day selected: {}", d),
+            CliError::DayExists(d) => write!(f, "Day {} already exists", d),
+            CliError::InvalidPart(p) => write!(f, "Invalid part selected: {}", p),
+            CliError::InvalidFormat(o) => write!(f, "Invalid format selected: {}", o),
+            CliError::InvalidJobs(j) => write!(f, "Invalid number of jobs: {}", j),
+            CliError::InvalidInterval(i) => write!(f, "Invalid interval: {}", i),
+            CliError::InvalidTrace(t) => write!(f, "Invalid trace target: {}", t),
+            CliError::MissingValue(o) => write!(f, "Missing value for {}", o),
+            CliError::UnknownOption(o) => write!(f, "Unknown option: {}", o),
+            CliError::UnexpectedArgument(a) => write!(f, "Unexpected argument: {}", a),
+            CliError::PathWithAll(o) => write!(f, "{} cannot be used when running all days", o),
+            CliError::StdinTwice => write!(f, "Only one of --input and --example can read stdin"),
+            CliError::Conflict(a, b) => write!(f, "{} cannot be used with {}", a, b),
+            CliError::Requires(a, b) => write!(f, "{} requires {}", a, b),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+pub fn parse_args<I>(args: I, known_days: &[u8]) -> Result<Command, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => parse_run(args, known_days),
+        Some("new-day") => parse_new_day(args, known_days),
+        Some("fetch") => parse_fetch(args),
+        Some("submit") => parse_submit(args, known_days),
+        Some("watch") => parse_watch(args, known_days),
+        Some("analyse") => parse_analyse(args, known_days),
+        Some("help") | Some("-h") | Some("--help") => Ok(Command::Help),
+        Some(other) => Err(CliError::UnknownCommand(other.to_string())),
+        None => Err(CliError::MissingCommand),
+    }
+}
+
+fn parse_day(arg: &str, known_days: &[u8]) -> Result<DaySelection, CliError> {
+    if arg == "all" {
+        return Ok(DaySelection::All);
+    }
+
+    match arg.parse::<u8>() {
+        Ok(day) if known_days.contains(&day) => Ok(DaySelection::Day(day)),
+        _ => Err(CliError::InvalidDay(arg.to_string())),
+    }
+}
+
+// Options choosing the inputs of a day, shared by run and watch. Returns false for anything else.
+fn parse_input_option<I>(arg: &str, args: &mut I, config: &mut RunConfig) -> Result<bool, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let mut value = || args.next().ok_or(CliError::MissingValue(arg.to_string()));
+
+    match arg {
+        "--part" | "-p" => {
+            let value = value()?;
+            config.part = match value.as_str() {
+                "1" => Some(1),
+                "2" => Some(2),
+                _ => return Err(CliError::InvalidPart(value)),
+            };
+        }
+        "--input" | "-i" => config.input = Some(InputSource::from_arg(&value()?)),
+        "--example" | "-e" => config.example = Some(InputSource::from_arg(&value()?)),
+        "--answers" | "-a" => config.answers = Some(PathBuf::from(value()?)),
+        "--data-dir" | "-d" => config.data_dir = Some(PathBuf::from(value()?)),
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn parse_run<I>(mut args: I, known_days: &[u8]) -> Result<Command, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let day = parse_day(&args.next().ok_or(CliError::MissingDay)?, known_days)?;
+    let mut config = RunConfig::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            _ if parse_input_option(&arg, &mut args, &mut config)? => {}
+            "--no-bench" => config.skip_bench = true,
+            "--format" | "-f" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.format = Format::parse(&value).ok_or(CliError::InvalidFormat(value))?;
+            }
+            "--jobs" | "-j" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.jobs = Some(value.parse().map_err(|_| CliError::InvalidJobs(value))?);
+            }
+            "--baseline" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.baseline = Some(PathBuf::from(value));
+            }
+            "--save-baseline" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.save_baseline = Some(PathBuf::from(value));
+            }
+            "--fail-on-regression" => config.fail_on_regression = true,
+            "--trace" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.trace = Some(Filter::parse(&value).map_err(CliError::InvalidTrace)?);
+            }
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg)),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+
+    if config.input == Some(InputSource::Stdin) && config.example == Some(InputSource::Stdin) {
+        return Err(CliError::StdinTwice);
+    }
+
+    if config.skip_bench {
+        if config.baseline.is_some() {
+            return Err(CliError::Conflict("--baseline", "--no-bench"));
+        }
+        if config.save_baseline.is_some() {
+            return Err(CliError::Conflict("--save-baseline", "--no-bench"));
+        }
+    }
+    if config.fail_on_regression && config.baseline.is_none() {
+        return Err(CliError::Requires("--fail-on-regression", "--baseline"));
+    }
+
+    if day == DaySelection::All {
+        if config.input.is_some() {
+            return Err(CliError::PathWithAll("--input".to_string()));
+        }
+        if config.example.is_some() {
+            return Err(CliError::PathWithAll("--example".to_string()));
+        }
+        if config.answers.is_some() {
+            return Err(CliError::PathWithAll("--answers".to_string()));
+        }
+    }
+
+    Ok(Command::Run(day, config))
+}
+
+fn parse_new_day<I>(mut args: I, known_days: &[u8]) -> Result<Command, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let arg = args.next().ok_or(CliError::MissingDay)?;
+    let day = match arg.parse::<u8>() {
+        Ok(day) if known_days.contains(&day) => return Err(CliError::DayExists(day)),
+        Ok(day @ 1..=25) => day,
+        _ => return Err(CliError::InvalidDay(arg)),
+    };
+
+    let mut config = NewDayConfig {
+        day,
+        name: format!("Day {}", day),
+        root: PathBuf::from(env!("CARGO_MANIFEST_DIR")),
+        data_dir: None,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--name" | "-n" => {
+                config.name = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+            }
+            "--root" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.root = PathBuf::from(value);
+            }
+            "--data-dir" | "-d" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.data_dir = Some(PathBuf::from(value));
+            }
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg)),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+
+    Ok(Command::NewDay(config))
+}
+
+// Options shared by the commands talking to the puzzle site, returns false for anything else.
+fn parse_site_option<I>(arg: &str, args: &mut I, site: &mut SiteConfig) -> Result<bool, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    match arg {
+        "--data-dir" | "-d" => {
+            let value = args.next().ok_or(CliError::MissingValue(arg.to_string()))?;
+            site.data_dir = Some(PathBuf::from(value));
+        }
+        "--base-url" => {
+            site.base_url = Some(args.next().ok_or(CliError::MissingValue(arg.to_string()))?);
+        }
+        "--session-file" => {
+            let value = args.next().ok_or(CliError::MissingValue(arg.to_string()))?;
+            site.session_file = Some(PathBuf::from(value));
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn parse_fetch<I>(mut args: I) -> Result<Command, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let arg = args.next().ok_or(CliError::MissingDay)?;
+    let day = match arg.parse::<u8>() {
+        Ok(day @ 1..=25) => day,
+        _ => return Err(CliError::InvalidDay(arg)),
+    };
+
+    let mut site = SiteConfig::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            _ if parse_site_option(&arg, &mut args, &mut site)? => {}
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg)),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+
+    Ok(Command::Fetch(FetchConfig { day, site }))
+}
+
+fn parse_submit<I>(mut args: I, known_days: &[u8]) -> Result<Command, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let arg = args.next().ok_or(CliError::MissingDay)?;
+    let day = match arg.parse::<u8>() {
+        Ok(day @ 1..=25) => day,
+        _ => return Err(CliError::InvalidDay(arg)),
+    };
+    let part = match args.next().as_deref() {
+        Some("1") => 1,
+        Some("2") => 2,
+        Some(other) => return Err(CliError::InvalidPart(other.to_string())),
+        None => return Err(CliError::MissingPart),
+    };
+
+    let mut config = SubmitConfig {
+        day,
+        part,
+        answer: None,
+        input: None,
+        wait: false,
+        site: SiteConfig::default(),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answer" => {
+                config.answer = Some(args.next().ok_or(CliError::MissingValue(arg.clone()))?);
+            }
+            "--input" | "-i" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.input = Some(InputSource::from_arg(&value));
+            }
+            "--wait" => config.wait = true,
+            _ if parse_site_option(&arg, &mut args, &mut config.site)? => {}
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg)),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+
+    // Without an answer it is computed, which needs the day to exist.
+    if config.answer.is_none() && !known_days.contains(&day) {
+        return Err(CliError::InvalidDay(day.to_string()));
+    }
+    if config.answer.is_some() && config.input.is_some() {
+        return Err(CliError::Conflict("--answer", "--input"));
+    }
+
+    Ok(Command::Submit(config))
+}
+
+fn parse_watch<I>(mut args: I, known_days: &[u8]) -> Result<Command, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let arg = args.next().ok_or(CliError::MissingDay)?;
+    let day = match parse_day(&arg, known_days)? {
+        DaySelection::Day(day) => day,
+        DaySelection::All => return Err(CliError::InvalidDay(arg)),
+    };
+
+    let mut config = WatchConfig {
+        day,
+        run: RunConfig {
+            skip_bench: true,
+            ..RunConfig::default()
+        },
+        interval: Duration::from_millis(500),
+        rebuild: false,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            _ if parse_input_option(&arg, &mut args, &mut config.run)? => {}
+            "--interval" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                let millis = value
+                    .parse()
+                    .map_err(|_| CliError::InvalidInterval(value))?;
+                config.interval = Duration::from_millis(millis);
+            }
+            "--rebuild" => config.rebuild = true,
+            "--trace" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.run.trace = Some(Filter::parse(&value).map_err(CliError::InvalidTrace)?);
+            }
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg)),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+
+    // Stdin can only be read once, there would be nothing to re-run with.
+    if config.run.input == Some(InputSource::Stdin) {
+        return Err(CliError::Conflict("--input -", "watch"));
+    }
+    if config.run.example == Some(InputSource::Stdin) {
+        return Err(CliError::Conflict("--example -", "watch"));
+    }
+
+    Ok(Command::Watch(config))
+}
+
+fn parse_analyse<I>(mut args: I, known_days: &[u8]) -> Result<Command, CliError>
+where
+    I: Iterator<Item = String>,
+{
+    let arg = args.next().ok_or(CliError::MissingDay)?;
+    let day = match parse_day(&arg, known_days)? {
+        DaySelection::Day(day) => day,
+        DaySelection::All => return Err(CliError::InvalidDay(arg)),
+    };
+
+    let mut config = AnalyseConfig {
+        day,
+        ..AnalyseConfig::default()
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.input = Some(InputSource::from_arg(&value));
+            }
+            "--data-dir" | "-d" => {
+                let value = args.next().ok_or(CliError::MissingValue(arg.clone()))?;
+                config.data_dir = Some(PathBuf::from(value));
+            }
+            _ if arg.starts_with('-') => return Err(CliError::UnknownOption(arg)),
+            _ => return Err(CliError::UnexpectedArgument(arg)),
+        }
+    }
+
+    Ok(Command::Analyse(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAYS: [u8; 3] = [1, 2, 3];
+
+    fn parse(args: &str) -> Result<Command, CliError> {
+        parse_args(args.split_whitespace().map(str::to_string), &DAYS)
+    }
+
+    #[test]
+    fn parse_commands() {
+        assert!(matches!(parse("help"), Ok(Command::Help)));
+        assert!(matches!(parse("--help"), Ok(Command::Help)));
+        assert!(matches!(parse("-h"), Ok(Command::Help)));
+
+        let Ok(Command::Run(DaySelection::Day(2), config)) =
+            parse("run 2 -p 1 --input - --format json --jobs 0 --no-bench --trace day2=trace")
+        else {
+            panic!("run 2 not parsed");
+        };
+        assert_eq!(config.part, Some(1));
+        assert_eq!(config.input, Some(InputSource::Stdin));
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.jobs, Some(0));
+        assert!(config.skip_bench && !config.runs_part(2));
+        assert_eq!(config.trace.as_ref().unwrap().to_string(), "day2=trace");
+        assert_eq!(config.answers_path(2), None);
+
+        let Ok(Command::Run(DaySelection::All, config)) =
+            parse("run all --baseline b.txt --fail-on-regression -d data2")
+        else {
+            panic!("run all not parsed");
+        };
+        assert_eq!(config.baseline, Some(PathBuf::from("b.txt")));
+        assert_eq!(
+            config.input_source(3),
+            InputSource::Path(PathBuf::from("data2/day3.txt"))
+        );
+
+        let Ok(Command::NewDay(config)) = parse("new-day 4 --name Lobby") else {
+            panic!("new-day not parsed");
+        };
+        assert_eq!((config.day, config.name.as_str()), (4, "Lobby"));
+
+        let Ok(Command::Submit(config)) = parse("submit 9 2 --answer 42 --wait") else {
+            panic!("submit not parsed");
+        };
+        assert_eq!((config.day, config.part, config.wait), (9, 2, true));
+        assert_eq!(config.answer.as_deref(), Some("42"));
+
+        let Ok(Command::Watch(config)) = parse("watch 1 --interval 100 --rebuild") else {
+            panic!("watch not parsed");
+        };
+        assert_eq!(config.interval, Duration::from_millis(100));
+        assert!(config.rebuild && config.run.skip_bench);
+
+        assert!(matches!(
+            parse("fetch 12 --base-url http://localhost"),
+            Ok(Command::Fetch(_))
+        ));
+        assert!(matches!(
+            parse("analyse 1 -i input.txt"),
+            Ok(Command::Analyse(_))
+        ));
+    }
+
+    #[test]
+    fn reject_arguments() {
+        let cases = [
+            ("", CliError::MissingCommand),
+            ("solve 1", CliError::UnknownCommand("solve".to_string())),
+            ("run", CliError::MissingDay),
+            ("run 4", CliError::InvalidDay("4".to_string())),
+            ("run one", CliError::InvalidDay("one".to_string())),
+            ("run 1 --part 3", CliError::InvalidPart("3".to_string())),
+            (
+                "run 1 --format xml",
+                CliError::InvalidFormat("xml".to_string()),
+            ),
+            (
+                "run 1 --jobs many",
+                CliError::InvalidJobs("many".to_string()),
+            ),
+            (
+                "run 1 --trace day1=loud",
+                CliError::InvalidTrace("day1=loud".to_string()),
+            ),
+            (
+                "run 1 --input",
+                CliError::MissingValue("--input".to_string()),
+            ),
+            (
+                "run 1 --verbose",
+                CliError::UnknownOption("--verbose".to_string()),
+            ),
+            ("run 1 2", CliError::UnexpectedArgument("2".to_string())),
+            (
+                "run all --input in.txt",
+                CliError::PathWithAll("--input".to_string()),
+            ),
+            ("run 1 -i - -e -", CliError::StdinTwice),
+            (
+                "run 1 --no-bench --baseline b.txt",
+                CliError::Conflict("--baseline", "--no-bench"),
+            ),
+            (
+                "run 1 --fail-on-regression",
+                CliError::Requires("--fail-on-regression", "--baseline"),
+            ),
+            ("new-day 2", CliError::DayExists(2)),
+            ("new-day 26", CliError::InvalidDay("26".to_string())),
+            ("fetch 0", CliError::InvalidDay("0".to_string())),
+            ("submit 1", CliError::MissingPart),
+            ("submit 1 3", CliError::InvalidPart("3".to_string())),
+            ("submit 9 1", CliError::InvalidDay("9".to_string())),
+            (
+                "submit 1 1 --answer 3 --input in.txt",
+                CliError::Conflict("--answer", "--input"),
+            ),
+            ("watch all", CliError::InvalidDay("all".to_string())),
+            (
+                "watch 1 --interval soon",
+                CliError::InvalidInterval("soon".to_string()),
+            ),
+            (
+                "watch 1 --input -",
+                CliError::Conflict("--input -", "watch"),
+            ),
+            (
+                "analyse 1 --part 1",
+                CliError::UnknownOption("--part".to_string()),
+            ),
+        ];
+
+        for (args, error) in cases {
+            match parse(args) {
+                Err(e) => assert_eq!(e, error, "{}", args),
+                Ok(command) => panic!("{} parsed as {:?}", args, command),
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Empty,
+    InvalidNumber,
+    InvalidChar(char),
+    InvalidDirection,
+    InvalidOperator,
+    InvalidRange,
+    FieldCount { expected: usize, found: usize },
+    RaggedRow { expected: usize, found: usize },
+    MissingSection(&'static str),
+    TooMany { limit: usize },
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseErrorKind::Empty => write!(f, "Empty input"),
+            ParseErrorKind::InvalidNumber => write!(f, "Invalid number"),
+            ParseErrorKind::InvalidChar(_) => write!(f, "Invalid char"),
+            ParseErrorKind::InvalidDirection => write!(f, "Invalid direction"),
+            ParseErrorKind::InvalidOperator => write!(f, "Invalid operator"),
+            ParseErrorKind::InvalidRange => write!(f, "Invalid range format"),
+            ParseErrorKind::FieldCount { expected, found } => {
+                write!(f, "Expected {} fields, got {}", expected, found)
+            }
+            ParseErrorKind::RaggedRow { expected, found } => {
+                write!(f, "Expected a row of {} entries, got {}", expected, found)
+            }
+            ParseErrorKind::MissingSection(section) => write!(f, "Missing {}", section),
+            ParseErrorKind::TooMany { limit } => write!(f, "More than {} entries", limit),
+        }
+    }
+}
+
+// A parse failure pointing at the offending text of the input. Line and column are 1-based,
+// the column counts chars, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub line_text: String,
+}
+
+impl ParseError {
+    // `span` has to be a slice of `contents`, which is how the parsers hand out their tokens.
+    pub fn at(contents: &str, span: &str, kind: ParseErrorKind) -> ParseError {
+        let offset = (span.as_ptr() as usize).wrapping_sub(contents.as_ptr() as usize);
+        debug_assert!(offset <= contents.len(), "span is not part of the input");
+
+        ParseError::at_offset(contents, offset.min(contents.len()), span, kind)
+    }
+
+    // For things that are missing, points right after the last non-blank line of the input.
+    pub fn eof(contents: &str, kind: ParseErrorKind) -> ParseError {
+        ParseError::at_offset(contents, contents.trim_end().len(), "", kind)
+    }
+
+    fn at_offset(contents: &str, offset: usize, span: &str, kind: ParseErrorKind) -> ParseError {
+        let before = &contents[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line_text = contents[line_start..].lines().next().unwrap_or("");
+
+        ParseError {
+            kind,
+            file: None,
+            line: before.matches('\n').count() + 1,
+            column: contents[line_start..offset].chars().count() + 1,
+            text: span.lines().next().unwrap_or("").to_string(),
+            line_text: line_text.to_string(),
+        }
+    }
+
+    pub fn with_file(mut self, file: &str) -> ParseError {
+        self.file = Some(file.to_string());
+        self
+    }
+
+    // Multi-line report with the offending line and carets under the offending text.
+    pub fn render(&self) -> String {
+        let line_num = self.line.to_string();
+        let pad = " ".repeat(line_num.len());
+        let carets = "^".repeat(self.text.chars().count().max(1));
+
+        format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            self.message(),
+            pad,
+            self.file.as_deref().unwrap_or("<input>"),
+            self.line,
+            self.column,
+            pad,
+            line_num,
+            self.line_text,
+            pad,
+            " ".repeat(self.column - 1),
+            carets
+        )
+    }
+
+    fn message(&self) -> String {
+        if self.text.is_empty() {
+            self.kind.to_string()
+        } else {
+            format!("{}: {}", self.kind, self.text)
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file)?;
+        }
+        write!(f, "{}:{}: {}", self.line, self.column, self.message())
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_span() {
+        let contents = "R10\nL5\nX7\n";
+        let span = &contents[7..9];
+
+        let e = ParseError::at(contents, span, ParseErrorKind::InvalidDirection);
+        assert_eq!((e.line, e.column), (3, 1));
+        assert_eq!(e.text, "X7");
+        assert_eq!(e.line_text, "X7");
+        assert_eq!(e.to_string(), "3:1: Invalid direction: X7");
+    }
+
+    #[test]
+    fn render_snippet() {
+        let contents = "1,2,3\n4,x,6";
+        let span = &contents[8..9];
+
+        let e = ParseError::at(contents, span, ParseErrorKind::InvalidNumber).with_file("in.txt");
+        assert_eq!(
+            e.render(),
+            "error: Invalid number: x\n --> in.txt:2:3\n  |\n2 | 4,x,6\n  |   ^"
+        );
+    }
+
+    #[test]
+    fn eof_points_after_last_line() {
+        let contents = "1-3\n5-7\n\n";
+
+        let e = ParseError::eof(contents, ParseErrorKind::MissingSection("ingredients"));
+        assert_eq!((e.line, e.column), (2, 4));
+    }
+}
@@ -0,0 +1,208 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::http;
+
+pub const SESSION_VAR: &str = "AOC25_SESSION";
+pub const BASE_URL_VAR: &str = "AOC25_BASE_URL";
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2025";
+
+// `$AOC25_BASE_URL`, else the puzzles of this year.
+pub fn default_base_url() -> String {
+    env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+// `$XDG_CONFIG_HOME/aoc25/session`, else `~/.config/aoc25/session`.
+pub fn default_session_file() -> Option<PathBuf> {
+    let config_dir = match env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(config_dir.join("aoc25").join("session"))
+}
+
+// The session cookie of the site, from `$AOC25_SESSION` or the first line of the session file.
+// It is only needed once something has to be requested, so a missing one is not an error yet.
+pub fn load_session(session_file: Option<&Path>) -> Result<Option<String>, Box<dyn Error>> {
+    if let Ok(session) = env::var(SESSION_VAR)
+        && !session.trim().is_empty()
+    {
+        return Ok(Some(session.trim().to_string()));
+    }
+
+    let Some(session_file) = session_file else {
+        return Ok(None);
+    };
+    match fs::read_to_string(session_file) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .next()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", session_file.display(), e).into()),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: Option<String>) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn cookie(&self) -> Result<String, Box<dyn Error>> {
+        match &self.session {
+            Some(session) => Ok(format!("session={}", session)),
+            None => Err(format!(
+                "No session token, set ${} or write it to {}",
+                SESSION_VAR,
+                default_session_file()
+                    .map_or("a session file".to_string(), |p| p.display().to_string())
+            )
+            .into()),
+        }
+    }
+
+    pub fn get(&self, path: &str) -> Result<http::Response, Box<dyn Error>> {
+        let cookie = self.cookie()?;
+        Ok(http::get(&self.url(path), &[("Cookie", &cookie)])?)
+    }
+
+    pub fn post(&self, path: &str, body: &str) -> Result<http::Response, Box<dyn Error>> {
+        let cookie = self.cookie()?;
+        Ok(http::post(&self.url(path), &[("Cookie", &cookie)], body)?)
+    }
+
+    pub fn input(&self, day: u8) -> Result<String, Box<dyn Error>> {
+        let response = self.get(&format!("/day/{}/input", day))?;
+
+        match response.status {
+            200 if !response.body.trim().is_empty() => Ok(response.body),
+            200 => Err(format!("The input of day {} is empty", day).into()),
+            404 => Err(format!("Day {} is not unlocked yet", day).into()),
+            400 | 401 | 403 => Err("The session token was rejected".into()),
+            status => Err(format!(
+                "Unexpected response {}: {}",
+                status,
+                response.body.lines().next().unwrap_or("")
+            )
+            .into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+// The input files in the data directory are the cache: an input that is already there is never
+// requested again. Empty files, as created by `new-day`, don't count.
+pub fn cached_input(data_dir: &Path, day: u8) -> Option<PathBuf> {
+    let file_path = data_dir.join(format!("day{}.txt", day));
+    let metadata = fs::metadata(&file_path).ok()?;
+
+    (metadata.is_file() && metadata.len() > 0).then_some(file_path)
+}
+
+pub fn fetch_input(client: &Client, data_dir: &Path, day: u8) -> Result<Fetched, Box<dyn Error>> {
+    if let Some(file_path) = cached_input(data_dir, day) {
+        return Ok(Fetched::Cached(file_path));
+    }
+
+    let input = client.input(day)?;
+
+    // Written next to the final file and renamed, so an interrupted download is never cached.
+    fs::create_dir_all(data_dir).map_err(|e| format!("{}: {}", data_dir.display(), e))?;
+    let file_path = data_dir.join(format!("day{}.txt", day));
+    let partial_path = data_dir.join(format!("day{}.txt.part", day));
+    fs::write(&partial_path, input).map_err(|e| format!("{}: {}", partial_path.display(), e))?;
+    fs::rename(&partial_path, &file_path).map_err(|e| format!("{}: {}", file_path.display(), e))?;
+
+    Ok(Fetched::Downloaded(file_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc25-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn download_once() {
+        let data_dir = temp_dir("fetch");
+        let server = mock::Server::start(vec![(200, "L68\nR48\n")]);
+        let client = Client::new(
+            &format!("{}/2025/", server.base_url),
+            Some("abc".to_string()),
+        );
+
+        let fetched = fetch_input(&client, &data_dir, 1).unwrap();
+        let file_path = data_dir.join("day1.txt");
+        assert_eq!(fetched, Fetched::Downloaded(file_path.clone()));
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "L68\nR48\n");
+
+        // The server only answers once, a second request would fail.
+        let fetched = fetch_input(&client, &data_dir, 1).unwrap();
+        assert_eq!(fetched, Fetched::Cached(file_path));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/2025/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc"));
+
+        fs::remove_dir_all(&data_dir).unwrap();
+    }
+
+    #[test]
+    fn cached_input_needs_no_session() {
+        let data_dir = temp_dir("cached");
+        fs::create_dir_all(&data_dir).unwrap();
+        fs::write(data_dir.join("day2.txt"), "11-22").unwrap();
+        fs::write(data_dir.join("day3.txt"), "").unwrap();
+
+        let client = Client::new("http://127.0.0.1:1", None);
+        assert!(matches!(
+            fetch_input(&client, &data_dir, 2),
+            Ok(Fetched::Cached(_))
+        ));
+        let e = fetch_input(&client, &data_dir, 3).unwrap_err();
+        assert!(e.to_string().starts_with("No session token"));
+
+        fs::remove_dir_all(&data_dir).unwrap();
+    }
+
+    #[test]
+    fn report_locked_day() {
+        let data_dir = temp_dir("locked");
+        let server = mock::Server::start(vec![(404, "Not Found")]);
+        let client = Client::new(&server.base_url, Some("abc".to_string()));
+
+        let e = fetch_input(&client, &data_dir, 12).unwrap_err();
+        assert_eq!(e.to_string(), "Day 12 is not unlocked yet");
+        assert!(!data_dir.join("day12.txt").exists());
+
+        server.requests();
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// Row and column.
+pub type Pos = (usize, usize);
+
+pub const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+pub const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+// What neighbours outside of the grid read as.
+#[derive(Debug)]
+pub enum Border<'a, T> {
+    Skip,
+    Fill(&'a T),
+    Wrap,
+}
+
+// Derived, these would need `T: Copy`, though only a reference is held.
+impl<T> Clone for Border<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Border<'_, T> {}
+
+// A rectangle of cells, stored row after row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    // `None` if the rows are not all equally long.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): Pos) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.0 * self.width + pos.1])
+    }
+
+    // The position `offset` away from `pos`, if that is inside the grid or `wrap` brings it back in.
+    pub fn step(&self, pos: Pos, offset: (isize, isize), wrap: bool) -> Option<Pos> {
+        let ((row, col), (d_row, d_col)) = (pos, offset);
+        if wrap {
+            if self.cells.is_empty() {
+                return None;
+            }
+            let row = (row as isize + d_row).rem_euclid(self.height as isize) as usize;
+            let col = (col as isize + d_col).rem_euclid(self.width as isize) as usize;
+            return Some((row, col));
+        }
+
+        let pos = (
+            row.checked_add_signed(d_row)?,
+            col.checked_add_signed(d_col)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    // The positions next to `pos` inside the grid, up, down, left and right.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |&offset| self.step(pos, offset, false))
+    }
+
+    // The positions next to `pos` inside the grid, diagonals included.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_AROUND
+            .iter()
+            .filter_map(move |&offset| self.step(pos, offset, false))
+    }
+
+    // The cells at `offsets` from `pos`, with the ones off the grid read as `border` says.
+    pub fn around<'a>(
+        &'a self,
+        pos: Pos,
+        offsets: &'a [(isize, isize)],
+        border: Border<'a, T>,
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        offsets.iter().filter_map(move |&offset| {
+            match (
+                self.step(pos, offset, matches!(border, Border::Wrap)),
+                border,
+            ) {
+                (Some(pos), _) => Some(&self[pos]),
+                (None, Border::Fill(value)) => Some(value),
+                (None, _) => None,
+            }
+        })
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    // By index rather than `chunks`, which would find no rows in a grid without columns.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    // One line per row, each cell drawn as the char `cell` gives it.
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&cell).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!(
+                "{:?} is outside of a {}x{} grid",
+                pos, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{:?} is outside of a {}x{} grid", pos, width, height),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap()
+    }
+
+    #[test]
+    fn index_and_iterate() {
+        let mut grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        grid[(0, 0)] = 7;
+
+        assert_eq!(grid.row(0), [7, 2, 3]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<u8>>(), [2, 5]);
+        assert_eq!(grid.positions().last(), Some((1, 2)));
+        assert_eq!(grid.to_string(), "723\n456");
+        assert_eq!(grid.transpose().to_string(), "74\n25\n36");
+        assert_eq!(
+            grid.map(|v| v % 2 == 0)
+                .render(|&even| if even { '#' } else { '.' }),
+            ".#.\n#.#"
+        );
+
+        assert!(Grid::from_rows(vec![vec![1], vec![]]).is_none());
+    }
+
+    #[test]
+    fn without_columns() {
+        let grid: Grid<u8> = Grid::from_rows(vec![vec![], vec![], vec![]]).unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 3));
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.to_string(), "\n\n");
+        assert_eq!(grid.render(|_| '#'), "\n\n");
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.get((0, 0)), None);
+
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (3, 0));
+        assert_eq!(transposed.rows().count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "(0, 3) is outside of a 3x2 grid")]
+    fn index_out_of_bounds() {
+        let _ = grid()[(0, 3)];
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = grid();
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<Pos>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8((0, 1)).count(), 5);
+        assert_eq!(
+            grid.neighbours8((1, 1)).map(|pos| grid[pos]).sum::<u8>(),
+            16
+        );
+
+        let around = |border| grid.around((0, 0), &ALL_AROUND, border).sum::<u8>();
+        assert_eq!(around(Border::Skip), 11);
+        assert_eq!(around(Border::Fill(&1)), 16);
+        // Wrapping around a 3x2 grid, the cells above are the ones below.
+        assert_eq!(around(Border::Wrap), 2 * (4 + 5 + 6) + 3 + 2);
+    }
+}
@@ -0,0 +1,527 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+// Just enough HTTP/1.1 to talk to the puzzle site. Plain http is spoken directly, https goes
+// through the system `curl` since there is no TLS in std.
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+const USER_AGENT: &str = concat!("aoc25/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+#[derive(Debug)]
+pub enum HttpError {
+    InvalidUrl(String),
+    Io(std::io::Error),
+    InvalidResponse(String),
+    Curl(String),
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::InvalidUrl(url) => write!(f, "Invalid URL: {}", url),
+            HttpError::Io(e) => write!(f, "Connection failed: {}", e),
+            HttpError::InvalidResponse(e) => write!(f, "Invalid response: {}", e),
+            HttpError::Curl(e) => write!(f, "curl failed: {}", e),
+        }
+    }
+}
+
+impl Error for HttpError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            HttpError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for HttpError {
+    fn from(e: std::io::Error) -> Self {
+        HttpError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scheme {
+    Http,
+    Https,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Url {
+    scheme: Scheme,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl Url {
+    fn parse(url: &str) -> Result<Url, HttpError> {
+        let invalid = || HttpError::InvalidUrl(url.to_string());
+
+        let (scheme, rest) = if let Some(rest) = url.strip_prefix("http://") {
+            (Scheme::Http, rest)
+        } else if let Some(rest) = url.strip_prefix("https://") {
+            (Scheme::Https, rest)
+        } else {
+            return Err(invalid());
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, "/"),
+        };
+        // IPv6 hosts are in brackets, the port is after them.
+        let (host, port) = match authority.strip_prefix('[') {
+            Some(rest) => {
+                let (host, port) = rest.split_once(']').ok_or_else(invalid)?;
+                match port {
+                    "" => (host, None),
+                    port => (host, Some(port.strip_prefix(':').ok_or_else(invalid)?)),
+                }
+            }
+            None => match authority.split_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        let port = match port {
+            Some(port) => port.parse().map_err(|_| invalid())?,
+            None if scheme == Scheme::Http => 80,
+            None => 443,
+        };
+        if host.is_empty() || (host.contains(':') && !authority.starts_with('[')) {
+            return Err(invalid());
+        }
+
+        Ok(Url {
+            scheme,
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+
+    // The port is left out when it is the default of plain http.
+    fn host_header(&self) -> String {
+        let host = match self.host.contains(':') {
+            true => format!("[{}]", self.host),
+            false => self.host.clone(),
+        };
+        match self.port {
+            80 => host,
+            port => format!("{}:{}", host, port),
+        }
+    }
+}
+
+pub fn get(url: &str, headers: &[(&str, &str)]) -> Result<Response, HttpError> {
+    request("GET", url, headers, None)
+}
+
+// Sends `body` as a url encoded form.
+pub fn post(url: &str, headers: &[(&str, &str)], body: &str) -> Result<Response, HttpError> {
+    request("POST", url, headers, Some(body))
+}
+
+fn request(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response, HttpError> {
+    let parsed = Url::parse(url)?;
+    match parsed.scheme {
+        Scheme::Http => request_plain(method, &parsed, headers, body),
+        Scheme::Https => request_curl(method, url, headers, body),
+    }
+}
+
+fn request_plain(
+    method: &str,
+    url: &Url,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response, HttpError> {
+    let mut stream = TcpStream::connect((url.host.as_str(), url.port))?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nConnection: close\r\n",
+        method,
+        url.path,
+        url.host_header(),
+        USER_AGENT
+    );
+    for (name, value) in headers.iter() {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    if let Some(body) = body {
+        request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    request.push_str("\r\n");
+    request.push_str(body.unwrap_or(""));
+
+    stream.write_all(request.as_bytes())?;
+    read_response(BufReader::new(stream))
+}
+
+fn read_response<R: BufRead>(mut reader: R) -> Result<Response, HttpError> {
+    let invalid = |e: &str| HttpError::InvalidResponse(e.to_string());
+
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse::<u16>().ok())
+        .ok_or_else(|| invalid(status_line.trim_end()))?;
+
+    let mut content_length = None;
+    let mut chunked = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(invalid("Connection closed in the headers"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(invalid(line));
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = Some(value.parse::<usize>().map_err(|_| invalid(line))?);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            chunked = value.eq_ignore_ascii_case("chunked");
+        }
+    }
+
+    let mut body = vec![];
+    if chunked {
+        loop {
+            let mut size_line = String::new();
+            reader.read_line(&mut size_line)?;
+            let size_hex = size_line.trim_end().split(';').next().unwrap_or("");
+            let size = usize::from_str_radix(size_hex, 16).map_err(|_| invalid("Chunk size"))?;
+            if size == 0 {
+                break;
+            }
+
+            let start = body.len();
+            body.resize(start + size, 0);
+            reader.read_exact(&mut body[start..])?;
+            let mut crlf = String::new();
+            reader.read_line(&mut crlf)?;
+        }
+    } else if let Some(length) = content_length {
+        body.resize(length, 0);
+        reader.read_exact(&mut body)?;
+    } else {
+        reader.read_to_end(&mut body)?;
+    }
+
+    let body = String::from_utf8(body).map_err(|_| invalid("Body is not UTF-8"))?;
+    Ok(Response { status, body })
+}
+
+// Headers go through a config file on stdin so the session cookie doesn't show up in the process
+// list.
+fn curl_config(headers: &[(&str, &str)], body: Option<&str>) -> String {
+    let mut config = format!("user-agent = {}\n", quote(USER_AGENT));
+    for (name, value) in headers.iter() {
+        config.push_str(&format!(
+            "header = {}\n",
+            quote(&format!("{}: {}", name, value))
+        ));
+    }
+    if let Some(body) = body {
+        config.push_str(&format!("data = {}\n", quote(body)));
+    }
+    config
+}
+
+// A double quoted value of a curl config file, where backslashes start escapes.
+fn quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn request_curl(
+    method: &str,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: Option<&str>,
+) -> Result<Response, HttpError> {
+    let config = curl_config(headers, body);
+
+    let mut child = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--config",
+            "-",
+            "--request",
+            method,
+        ])
+        .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+        .args(["--write-out", "\n%{http_code}", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| HttpError::Curl(e.to_string()))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(config.as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(HttpError::Curl(stderr.trim().to_string()));
+    }
+
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| HttpError::InvalidResponse("Body is not UTF-8".to_string()))?;
+    let (body, status) = stdout
+        .rsplit_once('\n')
+        .ok_or_else(|| HttpError::InvalidResponse(stdout.clone()))?;
+    let status = status
+        .parse()
+        .map_err(|_| HttpError::InvalidResponse(status.to_string()))?;
+
+    Ok(Response {
+        status,
+        body: body.to_string(),
+    })
+}
+
+// A one-shot HTTP server on a free local port, answering requests with canned responses in
+// order and handing back what it received.
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Request {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Request {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    pub struct Server {
+        pub base_url: String,
+        handle: JoinHandle<Vec<Request>>,
+    }
+
+    impl Server {
+        pub fn start(responses: Vec<(u16, &'static str)>) -> Server {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+            let handle = thread::spawn(move || {
+                let mut requests = vec![];
+                for (status, body) in responses {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    requests.push(read_request(&mut reader));
+
+                    let response = format!(
+                        "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    );
+                    reader.get_mut().write_all(response.as_bytes()).unwrap();
+                }
+                requests
+            });
+
+            Server { base_url, handle }
+        }
+
+        // Waits for all canned responses to be sent.
+        pub fn requests(self) -> Vec<Request> {
+            self.handle.join().unwrap()
+        }
+    }
+
+    fn read_request<R: BufRead>(reader: &mut R) -> Request {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let mut parts = line.split_whitespace();
+        let method = parts.next().unwrap().to_string();
+        let path = parts.next().unwrap().to_string();
+
+        let mut headers = vec![];
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once(':').unwrap();
+            headers.push((name.to_string(), value.trim().to_string()));
+        }
+
+        let length = headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+            .map_or(0, |(_, v)| v.parse().unwrap());
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        Request {
+            method,
+            path,
+            headers,
+            body: String::from_utf8(body).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_urls() {
+        let url = Url::parse("http://127.0.0.1:8080/2025/day/1").unwrap();
+        assert_eq!(url.scheme, Scheme::Http);
+        assert_eq!((url.host.as_str(), url.port), ("127.0.0.1", 8080));
+        assert_eq!(url.path, "/2025/day/1");
+
+        let url = Url::parse("https://adventofcode.com").unwrap();
+        assert_eq!(
+            (url.host.as_str(), url.port, url.path.as_str()),
+            ("adventofcode.com", 443, "/")
+        );
+
+        assert_eq!(
+            Url::parse("http://localhost/").unwrap().host_header(),
+            "localhost"
+        );
+        assert_eq!(
+            Url::parse("http://localhost:8080/").unwrap().host_header(),
+            "localhost:8080"
+        );
+
+        let url = Url::parse("http://[::1]:8080/2025").unwrap();
+        assert_eq!((url.host.as_str(), url.port), ("::1", 8080));
+        assert_eq!(url.host_header(), "[::1]:8080");
+        let url = Url::parse("http://[fe80::1]").unwrap();
+        assert_eq!(
+            (url.host.as_str(), url.port, url.path.as_str()),
+            ("fe80::1", 80, "/")
+        );
+        assert_eq!(url.host_header(), "[fe80::1]");
+        for url in [
+            "http://[::1",
+            "http://[::1]8080/",
+            "http://::1/",
+            "http://[]:80/",
+        ] {
+            assert!(Url::parse(url).is_err(), "{}", url);
+        }
+
+        assert!(Url::parse("ftp://example.com").is_err());
+        assert!(Url::parse("http://:80/").is_err());
+    }
+
+    #[test]
+    fn quote_curl_config() {
+        assert_eq!(quote(r#"a\"b\c"#), r#""a\\\"b\\c""#);
+        assert_eq!(quote("a\nb"), r#""a\nb""#);
+
+        let config = curl_config(&[("Cookie", r"session=ab\cd")], Some("level=1"));
+        assert!(
+            config.contains("header = \"Cookie: session=ab\\\\cd\"\n"),
+            "{}",
+            config
+        );
+        assert!(config.ends_with("data = \"level=1\"\n"), "{}", config);
+    }
+
+    #[test]
+    fn read_chunked_body() {
+        let raw = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1,2,\r\n3\r\n3\n4\r\n0\r\n\r\n";
+        let response = read_response(raw.as_bytes()).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "1,2,3\n4");
+    }
+
+    #[test]
+    fn round_trip_with_mock() {
+        let server = mock::Server::start(vec![(200, "hello"), (404, "")]);
+        let server_url = server.base_url.clone();
+
+        let response = get(&format!("{}/a", server.base_url), &[("Cookie", "x=1")]).unwrap();
+        assert_eq!(
+            response,
+            Response {
+                status: 200,
+                body: "hello".to_string()
+            }
+        );
+        let response = post(&format!("{}/b", server.base_url), &[], "level=1").unwrap();
+        assert_eq!(response.status, 404);
+
+        let requests = server.requests();
+        assert_eq!(
+            (requests[0].method.as_str(), requests[0].path.as_str()),
+            ("GET", "/a")
+        );
+        assert_eq!(requests[0].header("cookie"), Some("x=1"));
+        let host = server_url.strip_prefix("http://").unwrap();
+        assert_eq!(requests[0].header("host"), Some(host));
+        assert_eq!(
+            (requests[1].method.as_str(), requests[1].body.as_str()),
+            ("POST", "level=1")
+        );
+    }
+}
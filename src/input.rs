@@ -0,0 +1,145 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+pub const DATA_DIR_VAR: &str = "AOC25_DATA_DIR";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl InputSource {
+    // `-` is stdin, anything else a path.
+    pub fn from_arg(arg: &str) -> InputSource {
+        match arg {
+            "-" => InputSource::Stdin,
+            _ => InputSource::Path(PathBuf::from(arg)),
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Path(path) => fs::read_to_string(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e))),
+            InputSource::Stdin => {
+                let mut contents = String::new();
+                io::stdin().read_to_string(&mut contents)?;
+                Ok(contents)
+            }
+            InputSource::Text(text) => Ok(text.clone()),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::Text(_) => write!(f, "<memory>"),
+        }
+    }
+}
+
+// `$AOC25_DATA_DIR`, else `data/` in the working directory, else the `data/` next to the binary
+// or in the crate it was built in, so `cargo run` also finds its inputs when started from
+// somewhere else. Without any of those, `data/` in the working directory, where a missing input
+// shows up under the path that was looked for.
+pub fn default_data_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(DATA_DIR_VAR) {
+        return PathBuf::from(dir);
+    }
+
+    let local = Path::new("data");
+    if local.is_dir() {
+        return local.to_path_buf();
+    }
+
+    exe_data_dir().unwrap_or_else(|| local.to_path_buf())
+}
+
+fn exe_data_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let exe_dir = exe.parent()?;
+    // Cargo builds into `target/<profile>`, tests into `target/<profile>/deps`.
+    let crate_root = exe_dir
+        .ancestors()
+        .find(|dir| dir.file_name() == Some("target".as_ref()))
+        .and_then(Path::parent);
+
+    [Some(exe_dir), crate_root]
+        .into_iter()
+        .flatten()
+        .map(|dir| dir.join("data"))
+        .find(|dir| dir.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn sources_from_args() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("data/day1.txt"),
+            InputSource::Path(PathBuf::from("data/day1.txt"))
+        );
+        // Only a lone dash is stdin.
+        assert_eq!(
+            InputSource::from_arg("-input"),
+            InputSource::Path(PathBuf::from("-input"))
+        );
+
+        assert_eq!(InputSource::Stdin.to_string(), "<stdin>");
+        assert_eq!(InputSource::Text("R1".to_string()).to_string(), "<memory>");
+        assert_eq!(InputSource::from_arg("in.txt").to_string(), "in.txt");
+    }
+
+    #[test]
+    fn read_sources() {
+        let dir = env::temp_dir().join(format!("aoc25-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("day1.txt");
+        fs::write(&path, "R1\nL2\n").unwrap();
+
+        assert_eq!(InputSource::Path(path.clone()).read().unwrap(), "R1\nL2\n");
+        assert_eq!(InputSource::Text("L3".to_string()).read().unwrap(), "L3");
+
+        // The error names the file that is missing.
+        let missing = InputSource::Path(dir.join("day2.txt"));
+        let e = missing.read().unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::NotFound);
+        assert!(
+            e.to_string().starts_with(&format!("{}: ", missing)),
+            "{}",
+            e
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn data_dir() {
+        let dir = default_data_dir();
+        match env::var_os(DATA_DIR_VAR) {
+            Some(var) => assert_eq!(dir, PathBuf::from(var)),
+            None => assert!(dir.ends_with("data") && dir.is_dir(), "{}", dir.display()),
+        }
+
+        // Tests are built into the crate's `target/`, unless it was moved elsewhere.
+        let crate_root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        if env::current_exe()
+            .unwrap()
+            .starts_with(crate_root.join("target"))
+        {
+            assert_eq!(exe_data_dir(), Some(crate_root.join("data")));
+        }
+    }
+}
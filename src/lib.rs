@@ -0,0 +1,17 @@
+pub mod answers;
+pub mod baseline;
+pub mod bench;
+pub mod challenges;
+pub mod cli;
+pub mod error;
+pub mod fetch;
+pub mod grid;
+pub mod http;
+pub mod input;
+pub mod output;
+pub mod parse;
+pub mod runner;
+pub mod scaffold;
+pub mod submit;
+pub mod trace;
+pub mod watch;
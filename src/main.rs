@@ -1,32 +1,138 @@
-mod challenges;
-
-use challenges::{day1, day2, day3, day4, day5, day6, day7, day8};
-
-fn main() {
-    let day = 8; // Set to 0 to run all, or specific day number
-
-    if day == 0 {
-        // Run all days
-        println!("\n=== Running all days ===\n");
-        day1::entry();
-        day2::entry();
-        day3::entry();
-        day4::entry();
-        day5::entry();
-        day6::entry();
-        day7::entry();
+use std::env;
+use std::error::Error;
+use std::process::ExitCode;
+
+use aoc25::cli::{self, Command, DaySelection, SubmitConfig};
+use aoc25::fetch::{self, Client, Fetched};
+use aoc25::submit::{Outcome, Submitter};
+use aoc25::{challenges, runner, scaffold, trace, watch};
+
+fn submit(config: &SubmitConfig) -> Result<Outcome, Box<dyn Error>> {
+    let answer = match &config.answer {
+        Some(answer) => answer.clone(),
+        None => match challenges::find(config.day) {
+            Some(solution) => runner::answer(solution, &config.input_source(), config.part)?,
+            None => unreachable!("day {} was accepted by the argument parser", config.day),
+        },
+    };
+    println!(
+        "Answer for day {} part {}: {}",
+        config.day, config.part, answer
+    );
+
+    let site = &config.site;
+    let client = Client::new(
+        &site.base_url(),
+        fetch::load_session(site.session_file().as_deref())?,
+    );
+    let submitter = Submitter {
+        client: &client,
+        answers_path: config.answers_path(),
+        log_path: config.log_path(),
+        wait: config.wait,
+    };
+
+    submitter.submit(config.day, config.part, &answer)
+}
+
+fn main() -> ExitCode {
+    let registry = challenges::registry();
+    let days: Vec<u8> = registry.iter().map(|solution| solution.day()).collect();
+
+    let command = match cli::parse_args(env::args().skip(1), &days) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let filter = match &command {
+        Command::Run(_, config) => config.trace.clone(),
+        Command::Watch(config) => config.run.trace.clone(),
+        _ => None,
+    };
+    if let Some(filter) = filter {
+        trace::init(filter);
+    }
+
+    let ok = match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            true
+        }
+        Command::Run(DaySelection::All, config) => runner::run_and_print(&registry, &config),
+        Command::Run(DaySelection::Day(day), config) => match challenges::find(day) {
+            Some(solution) => runner::run_and_print(&[solution], &config),
+            None => unreachable!("day {} was accepted by the argument parser", day),
+        },
+        Command::NewDay(config) => {
+            match scaffold::new_day(&config.root, &config.data_dir(), config.day, &config.name) {
+                Ok(changed) => {
+                    for file_path in changed.iter() {
+                        println!("Wrote {}", file_path.display());
+                    }
+                    true
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    false
+                }
+            }
+        }
+        Command::Fetch(config) => {
+            let site = &config.site;
+            let fetched = fetch::load_session(site.session_file().as_deref()).and_then(|session| {
+                let client = Client::new(&site.base_url(), session);
+                fetch::fetch_input(&client, &site.data_dir(), config.day)
+            });
+            match fetched {
+                Ok(Fetched::Cached(file_path)) => {
+                    println!("Already downloaded: {}", file_path.display());
+                    true
+                }
+                Ok(Fetched::Downloaded(file_path)) => {
+                    println!("Wrote {}", file_path.display());
+                    true
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    false
+                }
+            }
+        }
+        Command::Watch(config) => watch::watch(&config),
+        Command::Analyse(config) => {
+            let analysis = match challenges::find(config.day) {
+                Some(solution) => runner::analyse(solution, &config.input_source()),
+                None => unreachable!("day {} was accepted by the argument parser", config.day),
+            };
+            match analysis {
+                Ok(analysis) => {
+                    println!("{}", analysis);
+                    true
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    false
+                }
+            }
+        }
+        Command::Submit(config) => match submit(&config) {
+            Ok(outcome) => {
+                println!("{}", outcome);
+                outcome == Outcome::Correct
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                false
+            }
+        },
+    };
+
+    if ok {
+        ExitCode::SUCCESS
     } else {
-        // Run specific day
-        match day {
-            1 => day1::entry(),
-            2 => day2::entry(),
-            3 => day3::entry(),
-            4 => day4::entry(),
-            5 => day5::entry(),
-            6 => day6::entry(),
-            7 => day7::entry(),
-            8 => day8::entry(),
-            _ => println!("Invalid day selected!: {}", day),
-        };
+        ExitCode::FAILURE
     }
 }
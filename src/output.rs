@@ -0,0 +1,384 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::baseline::Comparison;
+use crate::bench::Stats;
+use crate::challenges::InputKind;
+use crate::runner::{DayReport, Failure, InputReport, PartReport};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Format> {
+        match s {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            _ => None,
+        }
+    }
+}
+
+fn print_failure(prefix: &str, failure: &Failure) {
+    match &failure.snippet {
+        Some(snippet) => eprintln!("{}", snippet),
+        None => eprintln!("{}: {}", prefix, failure.message),
+    }
+}
+
+fn print_parse(report: &InputReport, label: &str, prefix: &str) {
+    if let Some(e) = &report.error {
+        print_failure(prefix, e);
+        return;
+    }
+
+    match (&report.parse_stats, report.parse_time) {
+        (Some(Ok(stats)), _) => println!("Parse time{}: {}", label, stats),
+        (Some(Err(e)), _) => print_failure(prefix, e),
+        (None, Some(duration)) => println!("Parse time{}: {:?}", label, duration),
+        (None, None) => {}
+    }
+
+    if let Some(comparison) = &report.parse_baseline {
+        println!("Parse baseline: {}", comparison);
+    }
+}
+
+pub fn print_text(report: &DayReport) {
+    println!("=== Day {}: {} ===", report.day, report.name);
+
+    print_parse(&report.example, " (test)", "Error in test");
+    if let Some(e) = &report.answers_error {
+        print_failure("Error reading answers", e);
+    }
+    print_parse(&report.input, "", "Error");
+
+    for part in report.parts.iter() {
+        println!("=== Challenge {} ===", part);
+
+        if let Some(example) = report.example.part(*part) {
+            match &example.answer {
+                Ok(result) => println!("Test result: {}", result),
+                Err(e) => print_failure("Error in test", e),
+            }
+        }
+
+        if let Some(input) = report.input.part(*part) {
+            match (&input.answer, input.status, &input.expected) {
+                (Ok(result), Some(status), Some(expected)) if !status.is_ok() => {
+                    println!("Result: {} [{}, expected {}]", result, status, expected)
+                }
+                (Ok(result), Some(status), _) => println!("Result: {} [{}]", result, status),
+                (Ok(result), None, _) => println!("Result: {}", result),
+                (Err(e), _, _) => print_failure("Error", e),
+            }
+
+            match &input.solve_stats {
+                Some(Ok(stats)) => println!("Solve time: {}", stats),
+                Some(Err(e)) => print_failure("Error", e),
+                None => {}
+            }
+            if let Some(comparison) = &input.solve_baseline {
+                println!("Solve baseline: {}", comparison);
+            }
+        }
+    }
+}
+
+// One flat row per day, part and input, shared by the structured formats.
+struct Record<'a> {
+    day: u8,
+    name: &'a str,
+    part: u8,
+    input: &'static str,
+    source: &'a str,
+    answer: Option<&'a str>,
+    status: Option<String>,
+    expected: Option<&'a str>,
+    error: Option<&'a str>,
+    parse_time: Option<Duration>,
+    parse_stats: Option<&'a Stats>,
+    parse_baseline: Option<&'a Comparison>,
+    solve_stats: Option<&'a Stats>,
+    solve_baseline: Option<&'a Comparison>,
+}
+
+fn record<'a>(day: &'a DayReport, input: &'a InputReport, part: u8) -> Record<'a> {
+    let part_report: Option<&PartReport> = input.part(part);
+
+    let error = input
+        .error
+        .as_ref()
+        .or(match &input.parse_stats {
+            Some(Err(e)) => Some(e),
+            _ => None,
+        })
+        .or(part_report.and_then(|p| p.answer.as_ref().err()))
+        .or(part_report.and_then(|p| match &p.solve_stats {
+            Some(Err(e)) => Some(e),
+            _ => None,
+        }))
+        .or(match input.kind {
+            InputKind::Real => day.answers_error.as_ref(),
+            InputKind::Example => None,
+        });
+
+    Record {
+        day: day.day,
+        name: day.name,
+        part,
+        input: match input.kind {
+            InputKind::Example => "example",
+            InputKind::Real => "real",
+        },
+        source: &input.source,
+        answer: part_report.and_then(|p| p.answer.as_deref().ok()),
+        status: part_report.and_then(|p| p.status).map(|s| s.to_string()),
+        expected: part_report.and_then(|p| p.expected.as_deref()),
+        error: error.map(|e| e.message.as_str()),
+        parse_time: input.parse_time,
+        parse_stats: input.parse_stats.as_ref().and_then(|s| s.as_ref().ok()),
+        parse_baseline: input.parse_baseline.as_ref(),
+        solve_stats: part_report
+            .and_then(|p| p.solve_stats.as_ref())
+            .and_then(|s| s.as_ref().ok()),
+        solve_baseline: part_report.and_then(|p| p.solve_baseline.as_ref()),
+    }
+}
+
+fn records(reports: &[DayReport]) -> Vec<Record<'_>> {
+    let mut records = vec![];
+
+    for report in reports.iter() {
+        for part in report.parts.iter() {
+            records.push(record(report, &report.example, *part));
+            records.push(record(report, &report.input, *part));
+        }
+    }
+
+    records
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn json_opt_string(s: Option<&str>) -> String {
+    s.map_or("null".to_string(), json_string)
+}
+
+fn json_stats(stats: Option<&Stats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            "{{\"iterations\": {}, \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"p95_ns\": {}, \"stddev_ns\": {}}}",
+            stats.iterations,
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.p95.as_nanos(),
+            stats.stddev.as_nanos()
+        ),
+        None => "null".to_string(),
+    }
+}
+
+fn json_comparison(comparison: Option<&Comparison>) -> String {
+    match comparison {
+        Some(comparison) => format!(
+            "{{\"baseline_median_ns\": {}, \"change_pct\": {:.2}, \"verdict\": {}}}",
+            comparison.baseline_median.as_nanos(),
+            comparison.relative() * 100.0,
+            json_string(&comparison.change.to_string())
+        ),
+        None => "null".to_string(),
+    }
+}
+
+pub fn json(reports: &[DayReport]) -> String {
+    let rows: Vec<String> = records(reports)
+        .iter()
+        .map(|r| {
+            format!(
+                "  {{\"day\": {}, \"name\": {}, \"part\": {}, \"input\": {}, \"source\": {}, \"answer\": {}, \"status\": {}, \"expected\": {}, \"error\": {}, \"parse_ns\": {}, \"parse\": {}, \"parse_baseline\": {}, \"solve\": {}, \"solve_baseline\": {}}}",
+                r.day,
+                json_string(r.name),
+                r.part,
+                json_string(r.input),
+                json_string(r.source),
+                json_opt_string(r.answer),
+                json_opt_string(r.status.as_deref()),
+                json_opt_string(r.expected),
+                json_opt_string(r.error),
+                r.parse_time
+                    .map_or("null".to_string(), |d| d.as_nanos().to_string()),
+                json_stats(r.parse_stats),
+                json_comparison(r.parse_baseline),
+                json_stats(r.solve_stats),
+                json_comparison(r.solve_baseline)
+            )
+        })
+        .collect();
+
+    if rows.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", rows.join(",\n"))
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn csv_stats(stats: Option<&Stats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            "{},{},{},{},{},{}",
+            stats.iterations,
+            stats.min.as_nanos(),
+            stats.median.as_nanos(),
+            stats.mean.as_nanos(),
+            stats.p95.as_nanos(),
+            stats.stddev.as_nanos()
+        ),
+        None => ",,,,,".to_string(),
+    }
+}
+
+fn csv_comparison(comparison: Option<&Comparison>) -> String {
+    match comparison {
+        Some(comparison) => format!(
+            "{},{:.2},{}",
+            comparison.baseline_median.as_nanos(),
+            comparison.relative() * 100.0,
+            comparison.change
+        ),
+        None => ",,".to_string(),
+    }
+}
+
+const CSV_HEADER: &str = "day,name,part,input,source,answer,status,expected,error,parse_ns,\
+parse_iterations,parse_min_ns,parse_median_ns,parse_mean_ns,parse_p95_ns,parse_stddev_ns,\
+parse_baseline_median_ns,parse_change_pct,parse_verdict,\
+solve_iterations,solve_min_ns,solve_median_ns,solve_mean_ns,solve_p95_ns,solve_stddev_ns,\
+solve_baseline_median_ns,solve_change_pct,solve_verdict";
+
+pub fn csv(reports: &[DayReport]) -> String {
+    let mut out = String::new();
+    out.push_str(CSV_HEADER);
+    out.push('\n');
+
+    for r in records(reports).iter() {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+            r.day,
+            csv_field(r.name),
+            r.part,
+            r.input,
+            csv_field(r.source),
+            csv_field(r.answer.unwrap_or("")),
+            r.status.as_deref().unwrap_or(""),
+            csv_field(r.expected.unwrap_or("")),
+            csv_field(r.error.unwrap_or("")),
+            r.parse_time
+                .map_or(String::new(), |d| d.as_nanos().to_string()),
+            csv_stats(r.parse_stats),
+            csv_comparison(r.parse_baseline),
+            csv_stats(r.solve_stats),
+            csv_comparison(r.solve_baseline)
+        );
+    }
+
+    out
+}
+
+// Reads back what `csv` wrote: rows of fields, with quoted fields unescaped.
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            ('\r', false) => {}
+            (c, _) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_json_strings() {
+        assert_eq!(json_string("a \"b\"\\\n"), "\"a \\\"b\\\"\\\\\\n\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn escape_csv_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn read_back_csv() {
+        let fields = ["plain", "a,b", "say \"hi\"", "two\nlines", ""];
+        let line: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        let text = format!("h1,h2,h3,h4,h5\n{}\n", line.join(","));
+
+        let rows = parse_csv(&text);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], fields);
+    }
+}
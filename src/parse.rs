@@ -0,0 +1,264 @@
+use std::str::FromStr;
+
+use crate::error::{ParseError, ParseErrorKind};
+
+// Parsers for the shapes puzzle inputs come in. They all take the whole input next to the piece
+// being parsed, which has to be a slice of it, so errors point at the right line and column.
+
+// The lines of `text`, skipping blank ones.
+pub fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.lines().filter(|line| !line.trim().is_empty())
+}
+
+pub fn number<T: FromStr>(contents: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(contents, token, ParseErrorKind::InvalidNumber))
+}
+
+// Numbers separated by `separator`, ignoring empty tokens, so trailing separators and runs of
+// spaces are fine.
+pub fn numbers<T: FromStr>(
+    contents: &str,
+    text: &str,
+    separator: char,
+) -> Result<Vec<T>, ParseError> {
+    text.split(separator)
+        .filter(|token| !token.is_empty())
+        .map(|token| number(contents, token))
+        .collect()
+}
+
+// Exactly `N` numbers separated by `separator`, like `1,2,3`.
+pub fn fields<T: FromStr, const N: usize>(
+    contents: &str,
+    text: &str,
+    separator: char,
+) -> Result<[T; N], ParseError> {
+    let numbers: Vec<T> = text
+        .split(separator)
+        .map(|token| number(contents, token))
+        .collect::<Result<_, _>>()?;
+
+    numbers.try_into().map_err(|numbers: Vec<T>| {
+        let kind = ParseErrorKind::FieldCount {
+            expected: N,
+            found: numbers.len(),
+        };
+        ParseError::at(contents, text, kind)
+    })
+}
+
+// An inclusive range written as `a-b`.
+pub fn range<T: FromStr>(contents: &str, text: &str) -> Result<(T, T), ParseError> {
+    match text.split_once('-') {
+        Some((start, end)) if !end.contains('-') => {
+            Ok((number(contents, start)?, number(contents, end)?))
+        }
+        _ => Err(ParseError::at(contents, text, ParseErrorKind::InvalidRange)),
+    }
+}
+
+// Ranges separated by `separator`, ignoring empty tokens.
+pub fn ranges<T: FromStr>(
+    contents: &str,
+    text: &str,
+    separator: char,
+) -> Result<Vec<(T, T)>, ParseError> {
+    text.split(separator)
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(|token| range(contents, token))
+        .collect()
+}
+
+// One row of single digits per line, like `987654321`.
+pub fn digit_rows(contents: &str, text: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    lines(text)
+        .map(|line| {
+            line.char_indices()
+                .map(|(i, c)| match c.to_digit(10) {
+                    Some(digit) => Ok(digit as u8),
+                    None => {
+                        let span = &line[i..i + c.len_utf8()];
+                        Err(ParseError::at(
+                            contents,
+                            span,
+                            ParseErrorKind::InvalidNumber,
+                        ))
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// One line of chars, each turned into a value by `cell`. Chars it returns `None` for are invalid.
+pub fn char_row<T>(
+    contents: &str,
+    line: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Vec<T>, ParseError> {
+    line.char_indices()
+        .map(|(i, c)| {
+            cell(c).ok_or_else(|| {
+                let span = &line[i..i + c.len_utf8()];
+                ParseError::at(contents, span, ParseErrorKind::InvalidChar(c))
+            })
+        })
+        .collect()
+}
+
+// A rectangle of chars, one `char_row` per line.
+pub fn char_grid<T>(
+    contents: &str,
+    text: &str,
+    cell: impl Fn(char) -> Option<T>,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut rows: Vec<Vec<T>> = vec![];
+
+    for line in lines(text) {
+        let row = char_row(contents, line, &cell)?;
+
+        if let Some(first) = rows.first()
+            && first.len() != row.len()
+        {
+            let kind = ParseErrorKind::RaggedRow {
+                expected: first.len(),
+                found: row.len(),
+            };
+            return Err(ParseError::at(contents, line, kind));
+        }
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+// Blocks of lines separated by blank lines.
+pub fn sections(text: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start: Option<usize> = None;
+    let mut end = 0;
+
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(s) = start.take() {
+                sections.push(&text[s..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\n', '\r']).len();
+        }
+        offset += line.len();
+    }
+    if let Some(s) = start {
+        sections.push(&text[s..end]);
+    }
+
+    sections
+}
+
+// Exactly as many sections as there are `names`, which are used to report the missing ones.
+pub fn named_sections<'a, const N: usize>(
+    contents: &str,
+    text: &'a str,
+    names: [&'static str; N],
+) -> Result<[&'a str; N], ParseError> {
+    let sections = sections(text);
+
+    if let Some(name) = names.get(sections.len()) {
+        return Err(ParseError::eof(
+            contents,
+            ParseErrorKind::MissingSection(name),
+        ));
+    }
+    if let Some(extra) = sections.get(N) {
+        let kind = ParseErrorKind::FieldCount {
+            expected: N,
+            found: sections.len(),
+        };
+        return Err(ParseError::at(contents, extra, kind));
+    }
+
+    Ok(std::array::from_fn(|i| sections[i]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_numbers() {
+        let contents = "1,2,,3,\n 4  5 6\n7,x";
+        let lines: Vec<&str> = lines(contents).collect();
+
+        assert_eq!(numbers::<u32>(contents, lines[0], ',').unwrap(), [1, 2, 3]);
+        assert_eq!(numbers::<u32>(contents, lines[1], ' ').unwrap(), [4, 5, 6]);
+        let e = numbers::<u32>(contents, lines[2], ',').unwrap_err();
+        assert_eq!(e.to_string(), "3:3: Invalid number: x");
+    }
+
+    #[test]
+    fn read_fields() {
+        let contents = "162,817,812\n57,618\n";
+        let lines: Vec<&str> = lines(contents).collect();
+
+        assert_eq!(
+            fields::<u32, 3>(contents, lines[0], ',').unwrap(),
+            [162, 817, 812]
+        );
+        let e = fields::<u32, 3>(contents, lines[1], ',').unwrap_err();
+        assert_eq!(e.to_string(), "2:1: Expected 3 fields, got 2: 57,618");
+    }
+
+    #[test]
+    fn read_ranges() {
+        let contents = "11-22,95-115,\n1-2-3";
+        let lines: Vec<&str> = lines(contents).collect();
+
+        assert_eq!(
+            ranges::<u64>(contents, lines[0], ',').unwrap(),
+            [(11, 22), (95, 115)]
+        );
+        let e = range::<u64>(contents, lines[1]).unwrap_err();
+        assert_eq!(e.to_string(), "2:1: Invalid range format: 1-2-3");
+    }
+
+    #[test]
+    fn read_digits_and_grids() {
+        let contents = "987\n81a\n";
+        let e = digit_rows(contents, contents).unwrap_err();
+        assert_eq!(e.to_string(), "2:3: Invalid number: a");
+        assert_eq!(digit_rows(contents, &contents[..4]).unwrap(), [[9, 8, 7]]);
+
+        let contents = "..@\n@.@\n.@\n";
+        let cell = |c| match c {
+            '.' => Some(false),
+            '@' => Some(true),
+            _ => None,
+        };
+        let e = char_grid(contents, contents, cell).unwrap_err();
+        assert_eq!(e.to_string(), "3:1: Expected a row of 3 entries, got 2: .@");
+        let grid = char_grid(contents, &contents[..8], cell).unwrap();
+        assert_eq!(grid, [[false, false, true], [true, false, true]]);
+        let e = char_grid("..#", "..#", cell).unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::InvalidChar('#'));
+    }
+
+    #[test]
+    fn split_sections() {
+        let contents = "3-5\n10-14\n\n\n1\n5\n";
+        assert_eq!(sections(contents), ["3-5\n10-14", "1\n5"]);
+
+        let [ranges, ids] = named_sections(contents, contents, ["ranges", "ids"]).unwrap();
+        assert_eq!((ranges, ids), ("3-5\n10-14", "1\n5"));
+
+        let e = named_sections(contents, &contents[..9], ["ranges", "ids"]).unwrap_err();
+        assert_eq!(e.kind, ParseErrorKind::MissingSection("ids"));
+        let contents = "1-2\n\n3\n\n4\n";
+        let e = named_sections(contents, contents, ["ranges", "ids"]).unwrap_err();
+        assert_eq!(e.to_string(), "5:1: Expected 2 fields, got 3: 4");
+    }
+}
@@ -0,0 +1,507 @@
+use std::any::Any;
+use std::error::Error;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::answers::{AnswerStore, Status};
+use crate::baseline::{Baseline, Bench, Change, Comparison};
+use crate::bench::{self, BenchConfig, Stats};
+use crate::challenges::{DynSolution, InputKind};
+use crate::cli::RunConfig;
+use crate::error::ParseError;
+use crate::input::InputSource;
+use crate::output::{self, Format};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub message: String,
+    // Caret snippet of the offending line for parse errors.
+    pub snippet: Option<String>,
+}
+
+impl Failure {
+    fn from_error(e: &(dyn Error + 'static)) -> Failure {
+        Failure {
+            message: e.to_string(),
+            snippet: e.downcast_ref::<ParseError>().map(|e| e.render()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub part: u8,
+    pub answer: Result<String, Failure>,
+    // Only set for the real input.
+    pub status: Option<Status>,
+    pub expected: Option<String>,
+    pub solve_stats: Option<Result<Stats, Failure>>,
+    pub solve_baseline: Option<Comparison>,
+}
+
+impl PartReport {
+    pub fn ok(&self) -> bool {
+        self.answer.is_ok()
+            && self.status.is_none_or(|s| s.is_ok())
+            && !matches!(self.solve_stats, Some(Err(_)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InputReport {
+    pub kind: InputKind,
+    pub source: String,
+    pub parse_time: Option<Duration>,
+    pub parse_stats: Option<Result<Stats, Failure>>,
+    pub parse_baseline: Option<Comparison>,
+    // Reading or parsing failed, there are no parts then.
+    pub error: Option<Failure>,
+    pub parts: Vec<PartReport>,
+}
+
+impl InputReport {
+    pub fn ok(&self) -> bool {
+        self.error.is_none()
+            && !matches!(self.parse_stats, Some(Err(_)))
+            && self.parts.iter().all(|p| p.ok())
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartReport> {
+        self.parts.iter().find(|p| p.part == part)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u8,
+    pub name: &'static str,
+    pub parts: Vec<u8>,
+    pub answers_error: Option<Failure>,
+    pub example: InputReport,
+    pub input: InputReport,
+}
+
+impl DayReport {
+    pub fn ok(&self) -> bool {
+        self.answers_error.is_none() && self.example.ok() && self.input.ok()
+    }
+
+    // Benchmarks of the real input, there are none for the example.
+    fn benches(&self) -> Vec<(Bench, Stats)> {
+        let parse = self.input.parse_stats.iter().map(|s| (Bench::Parse, s));
+        let parts = self
+            .input
+            .parts
+            .iter()
+            .filter_map(|p| p.solve_stats.as_ref().map(|s| (Bench::Part(p.part), s)));
+
+        parse
+            .chain(parts)
+            .filter_map(|(bench, stats)| stats.as_ref().ok().map(|s| (bench, *s)))
+            .collect()
+    }
+
+    pub fn regressed(&self) -> bool {
+        let parse = self.input.parse_baseline.iter();
+        let parts = self
+            .input
+            .parts
+            .iter()
+            .filter_map(|p| p.solve_baseline.as_ref());
+        parse.chain(parts).any(|c| c.change == Change::Regression)
+    }
+
+    pub fn compare(&mut self, baseline: &Baseline) {
+        let day = self.day;
+        if let Some(Ok(stats)) = &self.input.parse_stats {
+            self.input.parse_baseline = baseline.compare(day, Bench::Parse, stats);
+        }
+        for part in self.input.parts.iter_mut() {
+            if let Some(Ok(stats)) = &part.solve_stats {
+                part.solve_baseline = baseline.compare(day, Bench::Part(part.part), stats);
+            }
+        }
+    }
+}
+
+// The contents and parsed form of a real input, kept so it can be benchmarked once the answers
+// of every day are in.
+struct Parsed {
+    contents: String,
+    input: Box<dyn Any + Send>,
+}
+
+fn parse(
+    solution: &dyn DynSolution,
+    source: &InputSource,
+    contents: &str,
+    kind: InputKind,
+) -> Result<(Box<dyn Any + Send>, Duration), ParseError> {
+    let start = Instant::now();
+    let input = solution
+        .parse(contents, kind)
+        .map_err(|e| e.with_file(&source.to_string()))?;
+    let duration = start.elapsed();
+
+    Ok((input, duration))
+}
+
+fn run_part(
+    solution: &dyn DynSolution,
+    answers: Option<&AnswerStore>,
+    input: &dyn Any,
+    part: u8,
+) -> PartReport {
+    let answer = solution
+        .solve(input, part)
+        .map_err(|e| Failure::from_error(e.as_ref()));
+
+    let mut report = PartReport {
+        part,
+        answer,
+        status: None,
+        expected: None,
+        solve_stats: None,
+        solve_baseline: None,
+    };
+
+    // Only the real input is checked against the answer store.
+    if let (Some(answers), Ok(answer)) = (answers, &report.answer) {
+        report.status = Some(answers.check(part, answer));
+        report.expected = answers.part(part).and_then(|a| a.accepted.clone());
+    }
+
+    report
+}
+
+fn run_input(
+    solution: &dyn DynSolution,
+    config: &RunConfig,
+    source: &InputSource,
+    kind: InputKind,
+    answers: Option<&AnswerStore>,
+) -> (InputReport, Option<Parsed>) {
+    let mut report = InputReport {
+        kind,
+        source: source.to_string(),
+        parse_time: None,
+        parse_stats: None,
+        parse_baseline: None,
+        error: None,
+        parts: vec![],
+    };
+
+    let contents = match source.read() {
+        Ok(contents) => contents,
+        Err(e) => {
+            report.error = Some(Failure::from_error(&e));
+            return (report, None);
+        }
+    };
+
+    let input = match parse(solution, source, &contents, kind) {
+        Ok((input, duration)) => {
+            report.parse_time = Some(duration);
+            input
+        }
+        Err(e) => {
+            report.error = Some(Failure::from_error(&e));
+            return (report, None);
+        }
+    };
+
+    for part in [1, 2] {
+        if config.runs_part(part) {
+            let part_report = run_part(solution, answers, input.as_ref(), part);
+            report.parts.push(part_report);
+        }
+    }
+
+    (report, Some(Parsed { contents, input }))
+}
+
+// Benchmarks parsing and every part that produced an answer. Only ever run with nothing else
+// going on, so the timings of one day are not skewed by another.
+fn bench_input(solution: &dyn DynSolution, report: &mut InputReport, parsed: &Parsed) {
+    let config = BenchConfig::default();
+
+    let stats = bench::bench(&config, || {
+        Ok(solution.parse(&parsed.contents, report.kind)?)
+    });
+    report.parse_stats = Some(stats.map_err(|e| Failure::from_error(&e)));
+
+    for part_report in report.parts.iter_mut() {
+        if part_report.answer.is_ok() {
+            let input = parsed.input.as_ref();
+            let stats = bench::bench(&config, || solution.solve(input, part_report.part));
+            part_report.solve_stats = Some(stats.map_err(|e| Failure::from_error(&e)));
+        }
+    }
+}
+
+// Computes the answers of a day without benchmarking, with the example and the real input on
+// separate threads if `parallel` is set.
+fn solve(
+    solution: &dyn DynSolution,
+    config: &RunConfig,
+    parallel: bool,
+) -> (DayReport, Option<Parsed>) {
+    let day = solution.day();
+
+    let mut answers_error = None;
+    let answers = match config.answers_path(day) {
+        Some(file_path) => AnswerStore::load(&file_path).unwrap_or_else(|e| {
+            answers_error = Some(Failure::from_error(e.as_ref()));
+            AnswerStore::default()
+        }),
+        None => AnswerStore::default(),
+    };
+
+    let example_source = config.example_source(day);
+    let input_source = config.input_source(day);
+    let run_example = || run_input(solution, config, &example_source, InputKind::Example, None);
+    let run_real = || {
+        run_input(
+            solution,
+            config,
+            &input_source,
+            InputKind::Real,
+            Some(&answers),
+        )
+    };
+
+    let ((example, _), (input, parsed)) = if parallel {
+        thread::scope(|s| {
+            let example = s.spawn(run_example);
+            let real = run_real();
+            (example.join().expect("example thread panicked"), real)
+        })
+    } else {
+        (run_example(), run_real())
+    };
+
+    let report = DayReport {
+        day,
+        name: solution.name(),
+        parts: [1, 2]
+            .into_iter()
+            .filter(|p| config.runs_part(*p))
+            .collect(),
+        answers_error,
+        example,
+        input,
+    };
+
+    (report, parsed)
+}
+
+fn bench_day(
+    solution: &dyn DynSolution,
+    config: &RunConfig,
+    day: (DayReport, Option<Parsed>),
+) -> DayReport {
+    let (mut report, parsed) = day;
+
+    if let (false, Some(parsed)) = (config.skip_bench, parsed) {
+        bench_input(solution, &mut report.input, &parsed);
+    }
+
+    report
+}
+
+// The answer of one part for the real input at `source`, without any checks or timing.
+pub fn answer(
+    solution: &dyn DynSolution,
+    source: &InputSource,
+    part: u8,
+) -> Result<String, Box<dyn Error>> {
+    let contents = source.read()?;
+    let (input, _) = parse(solution, source, &contents, InputKind::Real)?;
+
+    solution.solve(input.as_ref(), part)
+}
+
+// What the day has to say about the real input at `source`, besides the answers.
+pub fn analyse(solution: &dyn DynSolution, source: &InputSource) -> Result<String, Box<dyn Error>> {
+    let contents = source.read()?;
+    let (input, _) = parse(solution, source, &contents, InputKind::Real)?;
+
+    let analysis = solution.analyse(input.as_ref())?;
+    analysis.ok_or_else(|| format!("Day {} has no analysis", solution.day()).into())
+}
+
+pub fn run(solution: &dyn DynSolution, config: &RunConfig) -> DayReport {
+    bench_day(solution, config, solve(solution, config, false))
+}
+
+// Solves the days on a pool of `jobs` threads, handing out one day at a time. The results are
+// returned in the order of `solutions`.
+fn solve_parallel(
+    solutions: &[&dyn DynSolution],
+    config: &RunConfig,
+    jobs: usize,
+) -> Vec<(DayReport, Option<Parsed>)> {
+    let next = AtomicUsize::new(0);
+
+    let mut results: Vec<_> = thread::scope(|s| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut solved = vec![];
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(solution) = solutions.get(index) else {
+                            break;
+                        };
+                        solved.push((index, solve(*solution, config, true)));
+                    }
+                    solved
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("worker thread panicked"))
+            .collect()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+// Adds the benchmarks of `reports` to the baseline at `file_path`, keeping those of other days.
+fn save_baseline(file_path: &Path, reports: &[DayReport]) -> Result<(), Box<dyn Error>> {
+    let mut baseline = Baseline::load(file_path)?;
+    for report in reports.iter() {
+        for (bench, stats) in report.benches() {
+            baseline.record(report.day, bench, stats);
+        }
+    }
+    baseline.save(file_path)
+}
+
+// Runs the given days and prints them in the configured format. Text is printed as soon as a
+// day is done, the structured formats once all days are. Returns false if anything failed, or
+// with `--fail-on-regression` if a benchmark got slower than its baseline.
+//
+// With more than one job the answers of all days are computed in parallel first, the benchmarks
+// then run one day after the other so they don't compete for the CPU.
+pub fn run_and_print(solutions: &[&dyn DynSolution], config: &RunConfig) -> bool {
+    let baseline = match &config.baseline {
+        Some(file_path) => match Baseline::load(file_path) {
+            Ok(baseline) => Some(baseline),
+            Err(e) => {
+                eprintln!("Error reading baseline: {}", e);
+                return false;
+            }
+        },
+        None => None,
+    };
+
+    if config.format == Format::Text && solutions.len() > 1 {
+        println!("\n=== Running all days ===\n");
+    }
+
+    let jobs = config.jobs().min(solutions.len());
+    let mut solved = if jobs > 1 {
+        solve_parallel(solutions, config, jobs)
+            .into_iter()
+            .map(Some)
+            .collect()
+    } else {
+        vec![]
+    };
+
+    let mut reports = vec![];
+    for (i, solution) in solutions.iter().enumerate() {
+        let mut report = match solved.get_mut(i).and_then(Option::take) {
+            Some(day) => bench_day(*solution, config, day),
+            None => run(*solution, config),
+        };
+        if let Some(baseline) = &baseline {
+            report.compare(baseline);
+        }
+        if config.format == Format::Text {
+            output::print_text(&report);
+        }
+        reports.push(report);
+    }
+
+    match config.format {
+        Format::Text => {}
+        Format::Json => println!("{}", output::json(&reports)),
+        Format::Csv => print!("{}", output::csv(&reports)),
+    }
+
+    let mut ok = reports.iter().all(|r| r.ok());
+
+    if let Some(file_path) = &config.save_baseline
+        && let Err(e) = save_baseline(file_path, &reports)
+    {
+        eprintln!("Error saving baseline: {}", e);
+        ok = false;
+    }
+
+    if config.fail_on_regression && reports.iter().any(|r| r.regressed()) {
+        eprintln!("Benchmarks regressed against the baseline");
+        ok = false;
+    }
+
+    ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::challenges;
+    use crate::trace;
+
+    #[test]
+    fn parallel_keeps_day_order() {
+        let registry = challenges::registry();
+        let config = RunConfig {
+            data_dir: Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("data")),
+            skip_bench: true,
+            ..RunConfig::default()
+        };
+
+        let solved = solve_parallel(&registry, &config, 3);
+        assert_eq!(solved.len(), registry.len());
+
+        for (solution, (report, _)) in registry.iter().zip(solved.iter()) {
+            assert_eq!(report.day, solution.day());
+            // Checked against the answer files, a freshly scaffolded day has none yet.
+            for part in report.input.parts.iter() {
+                assert!(matches!(
+                    part.status,
+                    Some(Status::Pass | Status::Unverified)
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn trace_once_per_input() {
+        let solution = challenges::find(1).unwrap();
+        let config = RunConfig {
+            data_dir: Some(Path::new(env!("CARGO_MANIFEST_DIR")).join("data")),
+            skip_bench: true,
+            ..RunConfig::default()
+        };
+
+        let (_, traces) = trace::capture(|| run(solution, &config));
+        // Parsing and part 2 trace once each, for the example and the real input.
+        assert_eq!(traces.len(), 4, "{:?}", traces);
+
+        let config = RunConfig {
+            skip_bench: false,
+            ..config
+        };
+        let (report, benched) = trace::capture(|| run(solution, &config));
+        assert!(report.input.parse_stats.is_some());
+        assert_eq!(benched, traces);
+    }
+}
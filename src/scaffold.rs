@@ -0,0 +1,321 @@
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = r#"use std::error::Error;
+
+use super::{InputKind, Solution};
+use crate::error::{ParseError, ParseErrorKind};
+use crate::parse;
+
+// See `crate::parse` for numbers, ranges, digit rows, char grids and sections.
+pub fn read_input(contents: &str) -> Result<Vec<String>, ParseError> {
+    let lines: Vec<String> = parse::lines(contents).map(|line| line.to_string()).collect();
+
+    if lines.is_empty() {
+        return Err(ParseError::eof(contents, ParseErrorKind::Empty));
+    }
+
+    Ok(lines)
+}
+
+pub fn first_challenge(_lines: &[String]) -> Result<u64, Box<dyn Error>> {
+    Err("Not solved yet".into())
+}
+
+pub fn second_challenge(_lines: &[String]) -> Result<u64, Box<dyn Error>> {
+    Err("Not solved yet".into())
+}
+
+pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input = Vec<String>;
+    type Output = u64;
+
+    fn day(&self) -> u8 {
+        {day}
+    }
+
+    fn name(&self) -> &'static str {
+        "{name}"
+    }
+
+    fn parse(&self, contents: &str, _kind: InputKind) -> Result<Self::Input, ParseError> {
+        read_input(contents)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        first_challenge(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<Self::Output, Box<dyn Error>> {
+        second_challenge(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("{example}");
+
+    #[test]
+    #[ignore = "fill in the example and its answer"]
+    fn first_challenge_example() {
+        let input = Day{day}.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day{day}.part1(&input).unwrap(), 0);
+    }
+
+    #[test]
+    #[ignore = "fill in the example and its answer"]
+    fn second_challenge_example() {
+        let input = Day{day}.parse(EXAMPLE, InputKind::Example).unwrap();
+        assert_eq!(Day{day}.part2(&input).unwrap(), 0);
+    }
+}
+"#;
+
+// `example` is where `include_str!` finds the example, from `src/challenges`.
+pub fn module(day: u8, name: &str, example: &str) -> String {
+    TEMPLATE
+        .replace("{day}", &day.to_string())
+        .replace("{name}", &escape(name))
+        .replace("{example}", &escape(example))
+}
+
+// For a Rust string literal.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// The example of `day` in `data_dir` as seen from `src/challenges` under `root`: relative when
+// the data dir is inside the crate, so the crate can still be moved, absolute otherwise.
+fn example_include(root: &Path, data_dir: &Path, day: u8) -> Result<String, Box<dyn Error>> {
+    let file_name = format!("day{}_test.txt", day);
+    let root = std::path::absolute(root).map_err(|e| format!("{}: {}", root.display(), e))?;
+    let data_dir =
+        std::path::absolute(data_dir).map_err(|e| format!("{}: {}", data_dir.display(), e))?;
+
+    let path = match data_dir.strip_prefix(&root) {
+        Ok(relative) => {
+            let mut parts = vec!["..".to_string(), "..".to_string()];
+            for part in relative.components() {
+                parts.push(part.as_os_str().to_string_lossy().into_owned());
+            }
+            parts.push(file_name);
+            parts.join("/")
+        }
+        Err(_) => data_dir.join(file_name).to_string_lossy().into_owned(),
+    };
+
+    Ok(path)
+}
+
+// Number of the day a line of `challenges/mod.rs` is about, for the lines that declare the
+// module or list it in the registry.
+fn listed_day(line: &str) -> Option<u8> {
+    let line = line.trim();
+    let n = line
+        .strip_prefix("pub mod day")
+        .and_then(|rest| rest.strip_suffix(';'))
+        .or_else(|| {
+            line.strip_prefix("&day")
+                .and_then(|rest| rest.split_once("::"))
+                .map(|(n, _)| n)
+        })?;
+
+    n.parse().ok()
+}
+
+// Adds `pub mod dayN;` and `&dayN::DayN,` to `challenges/mod.rs`, each in order among its
+// siblings, both lists are kept sorted by day.
+pub fn register(mod_rs: &str, day: u8) -> Result<String, Box<dyn Error>> {
+    let declaration = format!("pub mod day{};", day);
+    let entry = format!("&day{}::Day{},", day, day);
+
+    let mut lines: Vec<String> = mod_rs.lines().map(|line| line.to_string()).collect();
+
+    for (prefix, new_line) in [("pub mod day", declaration), ("&day", entry)] {
+        let siblings: Vec<(usize, u8)> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.trim().starts_with(prefix))
+            .filter_map(|(i, line)| listed_day(line).map(|n| (i, n)))
+            .collect();
+
+        let Some(&(last, _)) = siblings.last() else {
+            return Err(format!("No `{}` lines found in challenges/mod.rs", prefix).into());
+        };
+        if siblings.iter().any(|&(_, n)| n == day) {
+            return Err(format!("Day {} is already registered", day).into());
+        }
+
+        let (index, template) = match siblings.iter().find(|&&(_, n)| n > day) {
+            Some(&(i, _)) => (i, i),
+            None => (last + 1, last),
+        };
+        let indent: String = lines[template]
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+        lines.insert(index, format!("{}{}", indent, new_line));
+    }
+
+    let mut registered = lines.join("\n");
+    if mod_rs.ends_with('\n') {
+        registered.push('\n');
+    }
+
+    Ok(registered)
+}
+
+// Creates an empty file, leaving any file that already exists alone.
+fn touch(file_path: &Path) -> Result<bool, Box<dyn Error>> {
+    match OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(file_path)
+    {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(format!("{}: {}", file_path.display(), e).into()),
+    }
+}
+
+// Generates `src/challenges/dayN.rs` under `root`, registers it and creates the input and example
+// files in `data_dir`. Returns the files that were created or changed.
+pub fn new_day(
+    root: &Path,
+    data_dir: &Path,
+    day: u8,
+    name: &str,
+) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let challenges = root.join("src").join("challenges");
+    let module_path = challenges.join(format!("day{}.rs", day));
+    let mod_rs_path = challenges.join("mod.rs");
+
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()).into());
+    }
+
+    let mod_rs = fs::read_to_string(&mod_rs_path)
+        .map_err(|e| format!("{}: {}", mod_rs_path.display(), e))?;
+    let registered = register(&mod_rs, day)?;
+
+    fs::create_dir_all(data_dir).map_err(|e| format!("{}: {}", data_dir.display(), e))?;
+    let mut changed = vec![];
+    for file_name in [format!("day{}.txt", day), format!("day{}_test.txt", day)] {
+        let file_path = data_dir.join(file_name);
+        if touch(&file_path)? {
+            changed.push(file_path);
+        }
+    }
+
+    let example = example_include(root, data_dir, day)?;
+    fs::write(&module_path, module(day, name, &example))
+        .map_err(|e| format!("{}: {}", module_path.display(), e))?;
+    changed.push(module_path);
+    fs::write(&mod_rs_path, registered).map_err(|e| format!("{}: {}", mod_rs_path.display(), e))?;
+    changed.push(mod_rs_path);
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOD_RS: &str = "\
+pub mod day1;
+pub mod day3;
+
+pub fn registry() -> Vec<&'static dyn DynSolution> {
+    vec![
+        &day1::Day1,
+        &day3::Day3,
+    ]
+}
+";
+
+    #[test]
+    fn register_in_order() {
+        let registered = register(MOD_RS, 2).unwrap();
+        assert_eq!(
+            registered,
+            "\
+pub mod day1;
+pub mod day2;
+pub mod day3;
+
+pub fn registry() -> Vec<&'static dyn DynSolution> {
+    vec![
+        &day1::Day1,
+        &day2::Day2,
+        &day3::Day3,
+    ]
+}
+"
+        );
+
+        let registered = register(MOD_RS, 12).unwrap();
+        assert!(registered.contains("pub mod day3;\npub mod day12;\n"));
+        assert!(registered.contains("        &day3::Day3,\n        &day12::Day12,\n    ]"));
+    }
+
+    #[test]
+    fn refuse_registered_day() {
+        assert!(register(MOD_RS, 3).is_err());
+        assert!(register("fn main() {}", 3).is_err());
+    }
+
+    #[test]
+    fn generate_module() {
+        let module = module(9, "Some \"Name\"", "../../data/day9_test.txt");
+        assert!(module.contains("pub struct Day9;"));
+        assert!(module.contains("        9\n"));
+        assert!(module.contains("\"Some \\\"Name\\\"\""));
+        assert!(module.contains("include_str!(\"../../data/day9_test.txt\")"));
+        assert!(!module.contains("{day}"));
+    }
+
+    #[test]
+    fn scaffold_into_tree() {
+        let root = std::env::temp_dir().join(format!("aoc25-scaffold-{}", std::process::id()));
+        let challenges = root.join("src").join("challenges");
+        let data_dir = root.join("data");
+        fs::create_dir_all(&challenges).unwrap();
+        fs::create_dir_all(&data_dir).unwrap();
+        fs::write(challenges.join("mod.rs"), MOD_RS).unwrap();
+        fs::write(data_dir.join("day2.txt"), "kept").unwrap();
+
+        let changed = new_day(&root, &data_dir, 2, "Test").unwrap();
+        assert_eq!(changed.len(), 3);
+        assert_eq!(
+            fs::read_to_string(data_dir.join("day2.txt")).unwrap(),
+            "kept"
+        );
+        assert_eq!(
+            fs::read_to_string(data_dir.join("day2_test.txt")).unwrap(),
+            ""
+        );
+        let module = fs::read_to_string(challenges.join("day2.rs")).unwrap();
+        assert!(module.contains("include_str!(\"../../data/day2_test.txt\")"));
+        assert!(new_day(&root, &data_dir, 2, "Test").is_err());
+
+        // The example is read from where the data dir is, inside the crate or not.
+        new_day(&root, &root.join("inputs").join("2025"), 4, "Test").unwrap();
+        let module = fs::read_to_string(challenges.join("day4.rs")).unwrap();
+        assert!(module.contains("include_str!(\"../../inputs/2025/day4_test.txt\")"));
+
+        let outside = root.with_extension("data");
+        new_day(&root, &outside, 5, "Test").unwrap();
+        let module = fs::read_to_string(challenges.join("day5.rs")).unwrap();
+        let example = escape(&outside.join("day5_test.txt").to_string_lossy());
+        assert!(module.contains(&format!("include_str!(\"{}\")", example)));
+        fs::remove_dir_all(&outside).unwrap();
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,535 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::answers::{AnswerStore, Status, Verdict};
+use crate::fetch::Client;
+
+// Every submission is logged next to the answers as `data/dayN_submissions.txt`, one line per
+// submission with the unix time, the outcome and until when the site asked us to wait:
+//
+//     # time part answer outcome wait_until
+//     1733400000 2 356439420655744 too-high 1733400060
+//     1733400075 2 352556672963116 correct -
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    TooRecent(Duration),
+    // The part is already solved or not unlocked yet.
+    WrongLevel,
+    Unknown(String),
+}
+
+impl Outcome {
+    fn label(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::Wrong {
+                hint: Some(Hint::TooHigh),
+                ..
+            } => "too-high",
+            Outcome::Wrong {
+                hint: Some(Hint::TooLow),
+                ..
+            } => "too-low",
+            Outcome::Wrong { hint: None, .. } => "wrong",
+            Outcome::TooRecent(_) => "too-recent",
+            Outcome::WrongLevel => "wrong-level",
+            Outcome::Unknown(_) => "unknown",
+        }
+    }
+
+    fn wait(&self) -> Option<Duration> {
+        match self {
+            Outcome::Wrong { wait, .. } => *wait,
+            Outcome::TooRecent(wait) => Some(*wait),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "That's the right answer"),
+            Outcome::Wrong { hint, wait } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", it is too high")?,
+                    Some(Hint::TooLow) => write!(f, ", it is too low")?,
+                    None => {}
+                }
+                if let Some(wait) = wait {
+                    write!(f, ", wait {}s before trying again", wait.as_secs())?;
+                }
+                Ok(())
+            }
+            Outcome::TooRecent(wait) => {
+                write!(f, "Answered too recently, wait {}s", wait.as_secs())
+            }
+            Outcome::WrongLevel => write!(f, "This part is already solved or still locked"),
+            Outcome::Unknown(text) => write!(f, "Unexpected response: {}", text),
+        }
+    }
+}
+
+// Seconds in "one minute", "5 minutes", "37s" or "1m 22s".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut seconds = 0;
+    let mut found = false;
+
+    for (i, word) in words.iter().enumerate() {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        let amount = |unit: u64| match words.get(i.wrapping_sub(1)).copied() {
+            Some("one") | Some("a") => Some(unit),
+            Some(n) => n.parse::<u64>().ok().map(|n| n * unit),
+            None => None,
+        };
+
+        let value = match word {
+            "minute" | "minutes" => amount(60),
+            "second" | "seconds" => amount(1),
+            _ => {
+                let (number, unit) = word.split_at(word.len().saturating_sub(1));
+                match (number.parse::<u64>(), unit) {
+                    (Ok(n), "m") => Some(n * 60),
+                    (Ok(n), "s") => Some(n),
+                    _ => None,
+                }
+            }
+        };
+
+        if let Some(value) = value {
+            seconds += value;
+            found = true;
+        }
+    }
+
+    found.then(|| Duration::from_secs(seconds))
+}
+
+// The site answers with a full page, the verdict is the text of its <article>.
+pub fn parse_outcome(body: &str) -> Outcome {
+    let article = match (body.find("<article"), body.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &body[start..end],
+        _ => body,
+    };
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        let hint = if text.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if text.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        let wait = text.find("wait").and_then(|i| parse_wait(&text[i..]));
+        Outcome::Wrong { hint, wait }
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text
+            .find("You have")
+            .and_then(|i| parse_wait(&text[i..]))
+            .unwrap_or(Duration::from_secs(60));
+        Outcome::TooRecent(wait)
+    } else if text.contains("solving the right level") {
+        Outcome::WrongLevel
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub time: u64,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: String,
+    pub wait_until: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    pub fn load(file_path: &Path) -> Result<SubmissionLog, Box<dyn Error>> {
+        let contents = match fs::read_to_string(file_path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(SubmissionLog::default()),
+            Err(e) => return Err(format!("{}: {}", file_path.display(), e).into()),
+        };
+
+        let mut log = SubmissionLog::default();
+        for (line_num, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || format!("Invalid submission line {}: {}", line_num + 1, line);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [time, part, answer, outcome, wait_until] = fields[..] else {
+                return Err(invalid().into());
+            };
+
+            log.submissions.push(Submission {
+                time: time.parse().map_err(|_| invalid())?,
+                part: part.parse().map_err(|_| invalid())?,
+                answer: answer.to_string(),
+                outcome: outcome.to_string(),
+                wait_until: match wait_until {
+                    "-" => None,
+                    t => Some(t.parse().map_err(|_| invalid())?),
+                },
+            });
+        }
+
+        Ok(log)
+    }
+
+    pub fn append(file_path: &Path, submission: &Submission) -> Result<(), Box<dyn Error>> {
+        let is_new = !file_path.exists();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(file_path)
+            .map_err(|e| format!("{}: {}", file_path.display(), e))?;
+
+        let mut line = String::new();
+        if is_new {
+            line.push_str("# time part answer outcome wait_until\n");
+        }
+        line.push_str(&format!(
+            "{} {} {} {} {}\n",
+            submission.time,
+            submission.part,
+            submission.answer,
+            submission.outcome,
+            submission
+                .wait_until
+                .map_or("-".to_string(), |t| t.to_string())
+        ));
+
+        file.write_all(line.as_bytes())
+            .map_err(|e| format!("{}: {}", file_path.display(), e).into())
+    }
+
+    // Time left until the site accepts answers again.
+    pub fn cooldown(&self, now: u64) -> Option<Duration> {
+        let until = self.submissions.iter().filter_map(|s| s.wait_until).max()?;
+        (until > now).then(|| Duration::from_secs(until - now))
+    }
+}
+
+pub struct Submitter<'a> {
+    pub client: &'a Client,
+    pub answers_path: PathBuf,
+    pub log_path: PathBuf,
+    // Sleep through cooldowns instead of giving up.
+    pub wait: bool,
+}
+
+impl Submitter<'_> {
+    // Refuses answers the store already knows, otherwise posts the answer, logs the outcome and
+    // records the verdict in the answer store.
+    pub fn submit(&self, day: u8, part: u8, answer: &str) -> Result<Outcome, Box<dyn Error>> {
+        // The answer store and the log are split on whitespace, such an answer could not be
+        // read back.
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            return Err(format!("Invalid answer: {:?}", answer).into());
+        }
+
+        let answers = AnswerStore::load(&self.answers_path)?;
+        match answers.check(part, answer) {
+            Status::Pass => {
+                return Err(
+                    format!("{} is already the accepted answer of part {}", answer, part).into(),
+                );
+            }
+            Status::KnownWrong => {
+                return Err(format!("{} was already rejected for part {}", answer, part).into());
+            }
+            Status::Fail => {
+                let accepted = answers.part(part).and_then(|a| a.accepted.as_deref());
+                return Err(format!(
+                    "Part {} is already solved with {}",
+                    part,
+                    accepted.unwrap_or("another answer")
+                )
+                .into());
+            }
+            Status::Unverified => {}
+        }
+
+        let mut retried = false;
+        loop {
+            let log = SubmissionLog::load(&self.log_path)?;
+            if let Some(cooldown) = log.cooldown(now()) {
+                if !self.wait {
+                    return Err(
+                        format!("Wait {}s before submitting again", cooldown.as_secs()).into(),
+                    );
+                }
+                thread::sleep(cooldown);
+            }
+
+            let body = format!("level={}&answer={}", part, encode(answer));
+            let response = self.client.post(&format!("/day/{}/answer", day), &body)?;
+            if !response.is_success() {
+                return Err(format!("Unexpected response {}", response.status).into());
+            }
+            let outcome = parse_outcome(&response.body);
+
+            let time = now();
+            let submission = Submission {
+                time,
+                part,
+                answer: answer.to_string(),
+                outcome: outcome.label().to_string(),
+                wait_until: outcome.wait().map(|wait| time + wait.as_secs().max(1)),
+            };
+            SubmissionLog::append(&self.log_path, &submission)?;
+
+            match &outcome {
+                Outcome::Correct => {
+                    AnswerStore::append(&self.answers_path, part, Verdict::Accepted, answer)?
+                }
+                Outcome::Wrong { .. } => {
+                    AnswerStore::append(&self.answers_path, part, Verdict::Wrong, answer)?
+                }
+                // Nothing was judged, try once more after the wait if we're allowed to.
+                Outcome::TooRecent(_) if self.wait && !retried => {
+                    retried = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            return Ok(outcome);
+        }
+    }
+}
+
+// Percent-encoding for a form value.
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock;
+
+    const CORRECT: &str = "<main><article><p>That's the right answer! You are one gold star \
+closer to decorating the North Pole.</p></article></main>";
+    const TOO_HIGH: &str = "<main><article><p>That's not the right answer; your answer is too \
+high. If you're stuck, make sure you're using the full input data. Please wait one minute before \
+trying again. <a href=\"/2025/day/5\">[Return to Day 5]</a></p></article></main>";
+    const TOO_RECENT: &str = "<main><article><p>You gave an answer too recently; you have to wait \
+after submitting an answer before trying again.  You have 1m 22s left to wait. \
+<a href=\"/2025/day/5\">[Return to Day 5]</a></p></article></main>";
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc25-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_outcomes() {
+        assert_eq!(parse_outcome(CORRECT), Outcome::Correct);
+        assert_eq!(
+            parse_outcome(TOO_HIGH),
+            Outcome::Wrong {
+                hint: Some(Hint::TooHigh),
+                wait: Some(Duration::from_secs(60))
+            }
+        );
+        assert_eq!(
+            parse_outcome(TOO_RECENT),
+            Outcome::TooRecent(Duration::from_secs(82))
+        );
+        assert_eq!(
+            parse_outcome(
+                "<article><p>You don't seem to be solving the right level.</p></article>"
+            ),
+            Outcome::WrongLevel
+        );
+    }
+
+    #[test]
+    fn parse_waits() {
+        assert_eq!(
+            parse_wait("wait 5 minutes before"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            parse_wait("You have 37s left"),
+            Some(Duration::from_secs(37))
+        );
+        assert_eq!(parse_wait("nothing here"), None);
+    }
+
+    #[test]
+    fn submit_and_record() {
+        let dir = temp_dir("submit");
+        let server = mock::Server::start(vec![(200, TOO_HIGH)]);
+        let client = Client::new(&server.base_url, Some("abc".to_string()));
+        let submitter = Submitter {
+            client: &client,
+            answers_path: dir.join("day5_answers.txt"),
+            log_path: dir.join("day5_submissions.txt"),
+            wait: false,
+        };
+
+        let outcome = submitter.submit(5, 2, "356439420655744").unwrap();
+        assert!(matches!(
+            outcome,
+            Outcome::Wrong {
+                hint: Some(Hint::TooHigh),
+                ..
+            }
+        ));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/day/5/answer");
+        assert_eq!(requests[0].body, "level=2&answer=356439420655744");
+
+        // Recorded, so neither the same answer nor anything within the cooldown goes out again.
+        let answers = AnswerStore::load(&submitter.answers_path).unwrap();
+        assert_eq!(answers.check(2, "356439420655744"), Status::KnownWrong);
+        let e = submitter.submit(5, 2, "356439420655744").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "356439420655744 was already rejected for part 2"
+        );
+        let e = submitter.submit(5, 2, "1").unwrap_err();
+        assert!(e.to_string().starts_with("Wait "));
+
+        let log = SubmissionLog::load(&submitter.log_path).unwrap();
+        assert_eq!(log.submissions.len(), 1);
+        assert_eq!(log.submissions[0].outcome, "too-high");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuse_solved_part() {
+        let dir = temp_dir("solved");
+        let answers_path = dir.join("day1_answers.txt");
+        fs::write(&answers_path, "1 accepted 1066").unwrap();
+
+        // Nothing listens here, any request would fail differently.
+        let client = Client::new("http://127.0.0.1:1", Some("abc".to_string()));
+        let submitter = Submitter {
+            client: &client,
+            answers_path,
+            log_path: dir.join("day1_submissions.txt"),
+            wait: false,
+        };
+
+        let e = submitter.submit(1, 1, "1066").unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "1066 is already the accepted answer of part 1"
+        );
+        let e = submitter.submit(1, 1, "1067").unwrap_err();
+        assert_eq!(e.to_string(), "Part 1 is already solved with 1066");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refuse_invalid_answer() {
+        let dir = temp_dir("invalid");
+        // Nothing listens here, any request would fail differently.
+        let client = Client::new("http://127.0.0.1:1", Some("abc".to_string()));
+        let submitter = Submitter {
+            client: &client,
+            answers_path: dir.join("day3_answers.txt"),
+            log_path: dir.join("day3_submissions.txt"),
+            wait: false,
+        };
+
+        for (answer, error) in [
+            ("", "Invalid answer: \"\""),
+            ("12 34", "Invalid answer: \"12 34\""),
+            ("12\n", "Invalid answer: \"12\\n\""),
+        ] {
+            let e = submitter.submit(3, 1, answer).unwrap_err();
+            assert_eq!(e.to_string(), error);
+        }
+        assert!(!submitter.answers_path.exists() && !submitter.log_path.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn wait_out_too_recent() {
+        let dir = temp_dir("recent");
+        let recent =
+            "<article><p>You gave an answer too recently. You have 1s left to wait.</p></article>";
+        let server = mock::Server::start(vec![(200, recent), (200, CORRECT)]);
+        let client = Client::new(&server.base_url, Some("abc".to_string()));
+        let submitter = Submitter {
+            client: &client,
+            answers_path: dir.join("day2_answers.txt"),
+            log_path: dir.join("day2_submissions.txt"),
+            wait: true,
+        };
+
+        assert_eq!(submitter.submit(2, 1, "42").unwrap(), Outcome::Correct);
+        assert_eq!(server.requests().len(), 2);
+        let answers = AnswerStore::load(&submitter.answers_path).unwrap();
+        assert_eq!(answers.check(1, "42"), Status::Pass);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
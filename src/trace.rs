@@ -0,0 +1,237 @@
+use std::cell::{Cell, RefCell};
+use std::env;
+use std::fmt;
+use std::sync::OnceLock;
+
+pub const TRACE_VAR: &str = "AOC25_TRACE";
+
+// From least to most verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn parse(s: &str) -> Option<Level> {
+        match s {
+            "info" => Some(Level::Info),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Info => write!(f, "info"),
+            Level::Debug => write!(f, "debug"),
+            Level::Trace => write!(f, "trace"),
+        }
+    }
+}
+
+// Which targets to trace and how verbosely, written as `day5,day3=trace,all=info`. A target is
+// the last part of the module path, like `day5` or `runner`, `all` is every target. Without a
+// level, a target is traced up to debug.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    directives: Vec<(Option<String>, Level)>,
+}
+
+impl Filter {
+    pub fn parse(spec: &str) -> Result<Filter, String> {
+        let mut directives = vec![];
+
+        for item in spec
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+        {
+            let (target, level) = match item.split_once('=') {
+                Some((target, level)) => (target, Level::parse(level).ok_or(item)?),
+                None => (item, Level::Debug),
+            };
+            if target.is_empty()
+                || !target
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return Err(item.to_string());
+            }
+
+            let target = Some(target.to_string()).filter(|target| target != "all");
+            directives.push((target, level));
+        }
+
+        Ok(Filter { directives })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    // The most verbose level traced for `target`. Naming the target beats `all`, and a later
+    // directive beats an earlier one.
+    pub fn level(&self, target: &str) -> Option<Level> {
+        let named = self
+            .directives
+            .iter()
+            .rfind(|(t, _)| t.as_deref() == Some(target));
+
+        named
+            .or_else(|| self.directives.iter().rfind(|(t, _)| t.is_none()))
+            .map(|(_, level)| *level)
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (target, level)) in self.directives.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}={}", target.as_deref().unwrap_or("all"), level)?;
+        }
+
+        Ok(())
+    }
+}
+
+static FILTER: OnceLock<Filter> = OnceLock::new();
+
+// Sets what gets traced, for the rest of the process. Without a call, `$AOC25_TRACE` is read on
+// the first trace, so tests can be traced too. Only the first filter counts.
+pub fn init(filter: Filter) {
+    let _ = FILTER.set(filter);
+}
+
+fn filter() -> &'static Filter {
+    FILTER.get_or_init(|| {
+        env::var(TRACE_VAR)
+            .ok()
+            .and_then(|spec| Filter::parse(&spec).ok())
+            .unwrap_or_default()
+    })
+}
+
+pub fn target(module_path: &str) -> &str {
+    module_path.rsplit("::").next().unwrap_or(module_path)
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+// Runs `f` without tracing on this thread, for benchmarks that would otherwise trace every
+// iteration and time the printing along with the solver.
+pub fn quiet<T>(f: impl FnOnce() -> T) -> T {
+    let was_quiet = QUIET.replace(true);
+    let result = f();
+    QUIET.set(was_quiet);
+    result
+}
+
+// Runs `f` with every level of every target traced on this thread, and returns the traces
+// instead of printing them.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    let outer = CAPTURED.replace(Some(vec![]));
+    let result = f();
+    let traces = CAPTURED.replace(outer).unwrap_or_default();
+    (result, traces)
+}
+
+pub fn enabled(level: Level, module_path: &str) -> bool {
+    if QUIET.get() {
+        return false;
+    }
+    if CAPTURED.with_borrow(Option::is_some) {
+        return true;
+    }
+
+    let filter = filter();
+    !filter.is_empty()
+        && filter
+            .level(target(module_path))
+            .is_some_and(|max| level <= max)
+}
+
+// Traces go to stderr, so they never mix with the JSON or CSV on stdout.
+pub fn emit(level: Level, module_path: &str, args: fmt::Arguments) {
+    let line = format!("[{} {}] {}", target(module_path), level, args);
+    CAPTURED.with_borrow_mut(|captured| match captured {
+        Some(traces) => traces.push(line),
+        None => eprintln!("{}", line),
+    });
+}
+
+// The arguments are only evaluated when the trace is enabled, so a disabled trace costs a check
+// of the filter and nothing else.
+#[macro_export]
+macro_rules! trace_at {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($level, module_path!()) {
+            $crate::trace::emit($level, module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::trace_at!($crate::trace::Level::Info, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::trace_at!($crate::trace::Level::Debug, $($arg)+) };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::trace_at!($crate::trace::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_filter() {
+        let filter = Filter::parse("day5, day3=trace,all=info").unwrap();
+        assert_eq!(filter.level("day5"), Some(Level::Debug));
+        assert_eq!(filter.level("day3"), Some(Level::Trace));
+        assert_eq!(filter.level("runner"), Some(Level::Info));
+        assert_eq!(filter.to_string(), "day5=debug,day3=trace,all=info");
+
+        let filter = Filter::parse("day5=trace,day5=info").unwrap();
+        assert_eq!(filter.level("day5"), Some(Level::Info));
+        assert_eq!(filter.level("day4"), None);
+
+        assert!(Filter::parse("").unwrap().is_empty());
+        assert_eq!(Filter::parse("day5=loud"), Err("day5=loud".to_string()));
+        assert_eq!(Filter::parse("=debug"), Err("=debug".to_string()));
+        assert_eq!(Filter::parse("day 5"), Err("day 5".to_string()));
+    }
+
+    #[test]
+    fn quiet_and_capture() {
+        let ((), traces) = capture(|| {
+            crate::debug!("one");
+            quiet(|| crate::trace!("hidden"));
+            crate::trace!("two {}", 2);
+        });
+        assert_eq!(traces, ["[tests debug] one", "[tests trace] two 2"]);
+
+        assert!(!quiet(|| capture(|| enabled(Level::Info, "day1")).0));
+    }
+
+    #[test]
+    fn target_of_module() {
+        assert_eq!(target("aoc25::challenges::day5"), "day5");
+        assert_eq!(target("aoc25"), "aoc25");
+    }
+}
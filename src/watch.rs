@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::SystemTime;
+
+use crate::challenges;
+use crate::cli::WatchConfig;
+use crate::input::InputSource;
+use crate::output;
+use crate::runner;
+
+// The answer of one part of one input, as reported by a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub value: Result<String, String>,
+    pub status: Option<String>,
+}
+
+impl Answer {
+    fn show(&self) -> String {
+        match (&self.value, &self.status) {
+            (Ok(value), Some(status)) => format!("{} [{}]", value, status),
+            (Ok(value), None) => value.clone(),
+            (Err(e), _) => format!("error: {}", e),
+        }
+    }
+}
+
+// Answers by input ("example" or "real") and part.
+pub type Snapshot = BTreeMap<(String, u8), Answer>;
+
+// Both the in-process and the rebuilt runs go through the CSV output, so they are compared the
+// same way.
+pub fn snapshot(csv: &str) -> Result<Snapshot, String> {
+    let rows = output::parse_csv(csv);
+    let header = rows.first().ok_or("No output")?;
+    let column = |name: &str| {
+        header
+            .iter()
+            .position(|h| h == name)
+            .ok_or(format!("No {} column in the output", name))
+    };
+    let (input, part, answer, status, error) = (
+        column("input")?,
+        column("part")?,
+        column("answer")?,
+        column("status")?,
+        column("error")?,
+    );
+
+    let mut snapshot = Snapshot::new();
+    for row in rows.iter().skip(1) {
+        let field = |i: usize| row.get(i).map_or("", |f| f.as_str());
+        let part = field(part)
+            .parse::<u8>()
+            .map_err(|_| format!("Invalid row: {:?}", row))?;
+
+        let value = match (field(answer), field(error)) {
+            (answer, "") => Ok(answer.to_string()),
+            (_, error) => Err(error.to_string()),
+        };
+        let status = Some(field(status).to_string()).filter(|s| !s.is_empty());
+        snapshot.insert((field(input).to_string(), part), Answer { value, status });
+    }
+
+    Ok(snapshot)
+}
+
+// One line per answer, saying how it changed since the previous run.
+pub fn diff(previous: Option<&Snapshot>, current: &Snapshot) -> Vec<String> {
+    current
+        .iter()
+        .map(|((input, part), answer)| {
+            let label = match input.as_str() {
+                "example" => format!("Example part {}", part),
+                _ => format!("Part {}", part),
+            };
+
+            match previous.and_then(|p| p.get(&(input.clone(), *part))) {
+                None => format!("{}: {}", label, answer.show()),
+                Some(before) if before == answer => {
+                    format!("{}: {} (unchanged)", label, answer.show())
+                }
+                Some(before) => format!("{}: {} -> {}", label, before.show(), answer.show()),
+            }
+        })
+        .collect()
+}
+
+// Polls modification times. Directories are watched with everything in them.
+pub struct Watcher {
+    paths: Vec<PathBuf>,
+    mtimes: BTreeMap<PathBuf, Option<SystemTime>>,
+}
+
+impl Watcher {
+    pub fn new(paths: Vec<PathBuf>) -> Watcher {
+        let mtimes = scan(&paths);
+        Watcher { paths, mtimes }
+    }
+
+    // Files created, changed or removed since the last call.
+    pub fn changed(&mut self) -> Vec<PathBuf> {
+        let mtimes = scan(&self.paths);
+
+        let mut changed: Vec<PathBuf> = mtimes
+            .iter()
+            .filter(|(path, mtime)| self.mtimes.get(*path) != Some(mtime))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.mtimes
+                .keys()
+                .filter(|path| !mtimes.contains_key(*path))
+                .cloned(),
+        );
+
+        self.mtimes = mtimes;
+        changed
+    }
+}
+
+fn scan(paths: &[PathBuf]) -> BTreeMap<PathBuf, Option<SystemTime>> {
+    let mut mtimes = BTreeMap::new();
+    let mut pending: Vec<PathBuf> = paths.to_vec();
+
+    while let Some(path) = pending.pop() {
+        match fs::read_dir(&path) {
+            Ok(entries) => pending.extend(entries.filter_map(|e| e.ok()).map(|e| e.path())),
+            // Missing files are watched too, for when they show up.
+            Err(_) => {
+                let mtime = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                mtimes.insert(path, mtime);
+            }
+        }
+    }
+
+    mtimes
+}
+
+fn watched_paths(config: &WatchConfig) -> Vec<PathBuf> {
+    let run = &config.run;
+    let mut paths = vec![];
+
+    for source in [run.input_source(config.day), run.example_source(config.day)] {
+        if let InputSource::Path(path) = source {
+            paths.push(path);
+        }
+    }
+    paths.extend(run.answers_path(config.day));
+
+    if config.rebuild {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        paths.push(root.join("src"));
+        paths.push(root.join("Cargo.toml"));
+    }
+
+    paths
+}
+
+fn run_in_process(config: &WatchConfig) -> Result<Snapshot, String> {
+    let solution = challenges::find(config.day).ok_or(format!("Unknown day {}", config.day))?;
+    let report = runner::run(solution, &config.run);
+
+    snapshot(&output::csv(&[report]))
+}
+
+// Rebuilds the crate and runs the day with the fresh binary. A failed build is returned as is,
+// so the compiler errors show up in the watch output.
+fn run_rebuilt(config: &WatchConfig) -> Result<Snapshot, String> {
+    let run = &config.run;
+    let manifest = Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml");
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+
+    let mut command = Command::new(cargo);
+    command.args([
+        "run",
+        "--quiet",
+        "--bin",
+        env!("CARGO_PKG_NAME"),
+        "--manifest-path",
+    ]);
+    command.arg(&manifest);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    command.args([
+        "--",
+        "run",
+        &config.day.to_string(),
+        "--no-bench",
+        "--format",
+        "csv",
+    ]);
+    if let Some(part) = run.part {
+        command.args(["--part", &part.to_string()]);
+    }
+    if let Some(InputSource::Path(path)) = &run.input {
+        command.arg("--input").arg(path);
+    }
+    if let Some(InputSource::Path(path)) = &run.example {
+        command.arg("--example").arg(path);
+    }
+    if let Some(path) = &run.answers {
+        command.arg("--answers").arg(path);
+    }
+    if let Some(path) = &run.data_dir {
+        command.arg("--data-dir").arg(path);
+    }
+    if let Some(filter) = &run.trace {
+        command.args(["--trace", &filter.to_string()]);
+    }
+
+    let output = command
+        .output()
+        .map_err(|e| format!("Could not run cargo: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stdout.trim().is_empty() {
+        return Err(stderr.trim_end().to_string());
+    }
+    // Traces and warnings.
+    eprint!("{}", stderr);
+
+    snapshot(&stdout)
+}
+
+// Runs the day whenever one of its files changes, until interrupted.
+pub fn watch(config: &WatchConfig) -> ! {
+    let mut watcher = Watcher::new(watched_paths(config));
+    let mut previous: Option<Snapshot> = None;
+    let mut changed: Vec<PathBuf> = vec![];
+    let mut run = 0;
+
+    loop {
+        run += 1;
+        println!("=== Day {}, run {} ===", config.day, run);
+        for path in changed.iter() {
+            println!("Changed: {}", path.display());
+        }
+
+        let result = if config.rebuild {
+            run_rebuilt(config)
+        } else {
+            run_in_process(config)
+        };
+        match result {
+            Ok(snapshot) => {
+                for line in diff(previous.as_ref(), &snapshot) {
+                    println!("{}", line);
+                }
+                previous = Some(snapshot);
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+        println!("Watching for changes...\n");
+
+        changed = loop {
+            thread::sleep(config.interval);
+            let changed = watcher.changed();
+            if !changed.is_empty() {
+                break changed;
+            }
+        };
+        // Editors tend to write in several steps, let them finish.
+        thread::sleep(config.interval);
+        changed.extend(watcher.changed());
+        changed.sort();
+        changed.dedup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = "\
+day,name,part,input,source,answer,status,expected,error,parse_ns
+1,Secret Entrance,1,example,data/day1_test.txt,3,,,,3145
+1,Secret Entrance,1,real,data/day1.txt,1066,PASS,1066,,139719
+1,Secret Entrance,2,example,data/day1_test.txt,,,,\"1:1: Invalid direction: X\",
+";
+
+    #[test]
+    fn read_snapshot() {
+        let snapshot = snapshot(CSV).unwrap();
+        assert_eq!(snapshot.len(), 3);
+
+        let real = &snapshot[&("real".to_string(), 1)];
+        assert_eq!(real.value, Ok("1066".to_string()));
+        assert_eq!(real.status.as_deref(), Some("PASS"));
+        let broken = &snapshot[&("example".to_string(), 2)];
+        assert_eq!(broken.value, Err("1:1: Invalid direction: X".to_string()));
+
+        assert!(super::snapshot("").is_err());
+    }
+
+    #[test]
+    fn diff_against_previous() {
+        let before = snapshot(CSV).unwrap();
+        let mut after = before.clone();
+        after.get_mut(&("example".to_string(), 1)).unwrap().value = Ok("4".to_string());
+
+        assert_eq!(
+            diff(Some(&before), &after),
+            [
+                "Example part 1: 3 -> 4",
+                "Example part 2: error: 1:1: Invalid direction: X (unchanged)",
+                "Part 1: 1066 [PASS] (unchanged)",
+            ]
+        );
+        assert_eq!(diff(None, &after)[0], "Example part 1: 4");
+    }
+
+    #[test]
+    fn notice_changes() {
+        let dir = env::temp_dir().join(format!("aoc25-watch-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("day1_test.txt");
+        let missing = dir.join("day1.txt");
+
+        let mut watcher = Watcher::new(vec![dir.clone(), missing.clone()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::write(&file_path, "R1").unwrap();
+        assert_eq!(watcher.changed(), std::slice::from_ref(&file_path));
+        fs::remove_file(&file_path).unwrap();
+        assert_eq!(watcher.changed(), [file_path]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}